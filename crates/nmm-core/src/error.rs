@@ -30,11 +30,116 @@ pub enum ModFormatError {
     #[error("Corrupt archive: {0}")]
     CorruptArchive(String),
 
+    /// The archive is password-protected.
+    #[error("Archive is encrypted")]
+    EncryptedArchive,
+
+    /// A format ID is empty or contains whitespace.
+    #[error("Invalid format ID: {0:?}")]
+    InvalidId(String),
+
+    /// A format with the same ID is already registered.
+    #[error("Format ID already registered: {0}")]
+    DuplicateId(String),
+
     /// An I/O error occurred.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
 
+/// Errors that can occur when working with game plugins.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    /// The plugin file is invalid.
+    #[error("Invalid plugin: {0}")]
+    Invalid(String),
+
+    /// The plugin file was not found.
+    #[error("Plugin not found: {0}")]
+    NotFound(PathBuf),
+
+    /// A master required by a plugin is not available.
+    #[error("Missing master: {0}")]
+    MissingMaster(String),
+
+    /// The game doesn't keep a plugin list file.
+    #[error("Game has no plugin list file")]
+    NoPluginListFile,
+
+    /// An I/O error occurred.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Errors that can occur when parsing a mod version string.
+#[derive(Debug, Error)]
+pub enum VersionParseError {
+    /// The version string was empty.
+    #[error("Version string is empty")]
+    Empty,
+
+    /// The version string is not valid semver.
+    #[error("Invalid version '{input}': {source}")]
+    Invalid {
+        /// The version string that failed to parse.
+        input: String,
+
+        /// The underlying semver error.
+        #[source]
+        source: semver::Error,
+    },
+}
+
+#[cfg(feature = "archive")]
+impl ModError {
+    /// Convert an error from reading `entry` out of a zip archive.
+    ///
+    /// Unlike the blanket [`From`] conversion, a missing entry becomes
+    /// [`ModError::FileNotFound`] carrying the entry's path.
+    pub fn from_zip_entry(err: zip::result::ZipError, entry: &str) -> Self {
+        match err {
+            zip::result::ZipError::FileNotFound => Self::FileNotFound(entry.to_string()),
+            other => other.into(),
+        }
+    }
+}
+
+/// Zip errors without an entry path. Use [`ModError::from_zip_entry`] when
+/// looking up a specific file so a missing entry is reported by name.
+#[cfg(feature = "archive")]
+impl From<zip::result::ZipError> for ModError {
+    fn from(err: zip::result::ZipError) -> Self {
+        use zip::result::ZipError;
+
+        match err {
+            ZipError::Io(e) => Self::Io(e),
+            other => Self::ArchiveError(other.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "archive")]
+impl From<zip::result::ZipError> for ModFormatError {
+    fn from(err: zip::result::ZipError) -> Self {
+        use zip::result::ZipError;
+
+        match err {
+            ZipError::Io(e) => Self::Io(e),
+            ZipError::InvalidArchive(msg) => Self::CorruptArchive(msg.into_owned()),
+            ZipError::InvalidPassword => Self::EncryptedArchive,
+            ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => Self::EncryptedArchive,
+            ZipError::UnsupportedArchive(_) => Self::UnsupportedFormat,
+            other => Self::CorruptArchive(other.to_string()),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown
+/// [`FormatConfidence`](crate::FormatConfidence) name.
+#[derive(Debug, Error)]
+#[error("Unknown format confidence: {0}")]
+pub struct ParseFormatConfidenceError(pub String);
+
 /// Errors that can occur when working with game modes.
 #[derive(Debug, Error)]
 pub enum GameModeError {
@@ -46,7 +151,104 @@ pub enum GameModeError {
     #[error("Unsupported game version: {0}")]
     UnsupportedVersion(String),
 
+    /// The game mode configuration is invalid.
+    #[error("Invalid game mode configuration: {0}")]
+    InvalidConfig(String),
+
     /// An I/O error occurred.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// An I/O error occurred while accessing a specific path.
+    #[error("IO error at {}: {source}", path.display())]
+    IoAt {
+        /// The path being accessed.
+        path: PathBuf,
+
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "archive")]
+    use zip::result::ZipError;
+
+    #[test]
+    fn test_game_mode_error_invalid_config_display() {
+        let err = GameModeError::InvalidConfig("primary color must be hex".into());
+        assert_eq!(
+            err.to_string(),
+            "Invalid game mode configuration: primary color must be hex"
+        );
+    }
+
+    #[test]
+    fn test_game_mode_error_io_at_display() {
+        let err = GameModeError::IoAt {
+            path: PathBuf::from("/games/skyrim/SkyrimSE.exe"),
+            source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied"),
+        };
+        assert_eq!(
+            err.to_string(),
+            "IO error at /games/skyrim/SkyrimSE.exe: access denied"
+        );
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn test_zip_error_to_mod_format_error() {
+        let err = ModFormatError::from(ZipError::InvalidArchive("bad header".into()));
+        assert!(matches!(err, ModFormatError::CorruptArchive(ref m) if m == "bad header"));
+
+        let err = ModFormatError::from(ZipError::UnsupportedArchive("multi-disk"));
+        assert!(matches!(err, ModFormatError::UnsupportedFormat));
+
+        let io = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "eof");
+        let err = ModFormatError::from(ZipError::Io(io));
+        assert!(
+            matches!(err, ModFormatError::Io(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof)
+        );
+
+        let err = ModFormatError::from(ZipError::InvalidPassword);
+        assert!(matches!(err, ModFormatError::EncryptedArchive));
+
+        let err = ModFormatError::from(ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED));
+        assert!(matches!(err, ModFormatError::EncryptedArchive));
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn test_zip_error_to_mod_error() {
+        let err = ModError::from_zip_entry(ZipError::FileNotFound, "fomod/info.xml");
+        assert_eq!(err.to_string(), "File not found in mod: fomod/info.xml");
+
+        let err = ModError::from_zip_entry(ZipError::InvalidPassword, "fomod/info.xml");
+        assert!(matches!(err, ModError::ArchiveError(_)));
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let err = ModError::from(ZipError::Io(io));
+        assert!(matches!(err, ModError::Io(_)));
+
+        let err = ModError::from(ZipError::InvalidArchive("truncated".into()));
+        assert!(matches!(err, ModError::ArchiveError(ref m) if m.contains("truncated")));
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn test_zip_error_question_mark() {
+        fn open(bytes: &[u8]) -> Result<usize, ModFormatError> {
+            let archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+            Ok(archive.len())
+        }
+
+        assert!(matches!(
+            open(b"not a zip file"),
+            Err(ModFormatError::CorruptArchive(_))
+        ));
+    }
 }
@@ -5,7 +5,9 @@
 //! - [`GameModeDescriptor`] - Static metadata about a game
 //! - [`GameMode`] - Runtime game mode with installation path
 //! - [`GameTheme`] - UI theming for the game
+//! - [`GameModeRegistry`] - Detects and creates game modes from a directory
 
+use crate::error::{GameModeError, PluginError};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
@@ -19,6 +21,89 @@ pub struct GameTheme {
     pub icon_path: Option<PathBuf>,
 }
 
+impl GameTheme {
+    /// Read the icon file, if one is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if `icon_path` is set but can't be read.
+    pub fn icon_bytes(&self) -> Result<Option<Vec<u8>>, std::io::Error> {
+        self.icon_path.as_deref().map(std::fs::read).transpose()
+    }
+
+    /// Detect the icon's image format from its contents.
+    ///
+    /// Returns `Ok(None)` if no icon is set or the format isn't recognized.
+    /// Only the first few bytes of the file are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if `icon_path` is set but can't be read.
+    pub fn icon_format(&self) -> Result<Option<IconFormat>, std::io::Error> {
+        use std::io::Read;
+
+        let Some(path) = &self.icon_path else {
+            return Ok(None);
+        };
+        let mut header = Vec::with_capacity(IconFormat::HEADER_LEN);
+        std::fs::File::open(path)?
+            .take(IconFormat::HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+        Ok(IconFormat::sniff(&header))
+    }
+}
+
+/// Icon image format, detected from file contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconFormat {
+    /// PNG image.
+    Png,
+
+    /// JPEG image.
+    Jpeg,
+
+    /// GIF image.
+    Gif,
+
+    /// Windows bitmap.
+    Bmp,
+
+    /// Windows icon.
+    Ico,
+}
+
+impl IconFormat {
+    /// Number of leading bytes [`sniff`](Self::sniff) needs.
+    pub const HEADER_LEN: usize = 8;
+
+    /// Detect the format from the leading magic bytes.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        const SIGNATURES: &[(&[u8], IconFormat)] = &[
+            (b"\x89PNG\r\n\x1a\n", IconFormat::Png),
+            (b"\xff\xd8\xff", IconFormat::Jpeg),
+            (b"GIF87a", IconFormat::Gif),
+            (b"GIF89a", IconFormat::Gif),
+            (b"BM", IconFormat::Bmp),
+            (b"\x00\x00\x01\x00", IconFormat::Ico),
+        ];
+
+        SIGNATURES
+            .iter()
+            .find(|(magic, _)| bytes.starts_with(magic))
+            .map(|(_, format)| *format)
+    }
+}
+
+/// How a mod file should be linked into the game directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkStrategy {
+    /// The file must be hardlinked (the game doesn't follow symlinks for it).
+    Hardlink,
+
+    /// The file can be symlinked.
+    Symlink,
+}
+
 /// Static metadata about a game mode.
 ///
 /// This trait provides information that doesn't depend on a specific
@@ -72,6 +157,29 @@ pub trait GameModeDescriptor: Send + Sync {
     /// Return an empty slice for games without a plugin system.
     fn plugin_extensions(&self) -> &[&str];
 
+    /// Check if a file has one of this game's plugin extensions.
+    ///
+    /// The comparison is case-insensitive, so `Mod.ESP` matches `.esp`.
+    fn is_plugin_extension(&self, path: &str) -> bool {
+        let Some(ext) = Path::new(path).extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+        self.plugin_extensions()
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    }
+
+    /// Plugin extensions as a lowercase set without leading dots.
+    ///
+    /// Build this once when classifying many files rather than calling
+    /// [`is_plugin_extension`](Self::is_plugin_extension) per file.
+    fn plugin_extension_set(&self) -> HashSet<String> {
+        self.plugin_extensions()
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_ascii_lowercase())
+            .collect()
+    }
+
     /// Critical plugins that cannot be disabled or reordered.
     ///
     /// These are typically the base game master files.
@@ -80,6 +188,21 @@ pub trait GameModeDescriptor: Send + Sync {
     /// Official DLC/expansion plugins in their correct load order.
     fn official_plugins(&self) -> &[&str];
 
+    /// Position of an official plugin in the canonical load order.
+    ///
+    /// The comparison is case-insensitive. Returns `None` for plugins not
+    /// listed in [`official_plugins`](Self::official_plugins).
+    fn official_plugin_index(&self, name: &str) -> Option<usize> {
+        self.official_plugins()
+            .iter()
+            .position(|p| p.eq_ignore_ascii_case(name))
+    }
+
+    /// Check if a plugin is an official DLC/expansion plugin.
+    fn is_official_plugin(&self, name: &str) -> bool {
+        self.official_plugin_index(name).is_some()
+    }
+
     /// Folders that indicate the root of mod content in archives.
     ///
     /// When extracting mods, NMM looks for these folders to determine
@@ -163,6 +286,179 @@ pub struct Plugin {
     pub author: Option<String>,
 }
 
+/// Find the masters of a plugin that aren't in the available set.
+///
+/// Names are compared case-insensitively. Missing masters are returned in
+/// the order the plugin lists them.
+///
+/// # Example
+///
+/// ```rust
+/// use nmm_core::{missing_masters, Plugin};
+/// use std::collections::HashSet;
+///
+/// let plugin = Plugin {
+///     path: "Data/MyMod.esp".into(),
+///     filename: "MyMod.esp".into(),
+///     is_master: false,
+///     is_light: false,
+///     masters: vec!["Skyrim.esm".into(), "Dawnguard.esm".into()],
+///     description: None,
+///     author: None,
+/// };
+///
+/// let available: HashSet<String> = ["Skyrim.esm".to_string()].into_iter().collect();
+/// assert_eq!(missing_masters(&plugin, &available), vec!["Dawnguard.esm"]);
+/// ```
+pub fn missing_masters(plugin: &Plugin, available: &HashSet<String>) -> Vec<String> {
+    let available: HashSet<String> = available.iter().map(|a| a.to_lowercase()).collect();
+    missing_masters_lowercase(plugin, &available)
+}
+
+/// [`missing_masters`] against a set that's already lowercased.
+fn missing_masters_lowercase(plugin: &Plugin, available: &HashSet<String>) -> Vec<String> {
+    plugin
+        .masters
+        .iter()
+        .filter(|m| !available.contains(&m.to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+/// Parse a Bethesda-style `plugins.txt` load order.
+///
+/// Each non-blank line names a plugin; a leading `*` marks it active.
+/// Lines starting with `#` are comments, and a lone `*` is skipped. Returns `(filename, active)`
+/// pairs in load order.
+///
+/// # Example
+///
+/// ```rust
+/// use nmm_core::parse_plugins_txt;
+///
+/// let entries = parse_plugins_txt("# comment\r\n*Skyrim.esm\r\nMyMod.esp\r\n");
+/// assert_eq!(
+///     entries,
+///     vec![("Skyrim.esm".to_string(), true), ("MyMod.esp".to_string(), false)]
+/// );
+/// ```
+pub fn parse_plugins_txt(content: &str) -> Vec<(String, bool)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.strip_prefix('*') {
+            Some(name) => (name.trim().to_string(), true),
+            None => (line.to_string(), false),
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// Format a load order as `plugins.txt` content.
+///
+/// The inverse of [`parse_plugins_txt`]. Uses CRLF line endings, as the
+/// games do.
+///
+/// # Errors
+///
+/// Returns [`PluginError::Invalid`] if a name contains a line break, which
+/// would split it across lines.
+pub fn format_plugins_txt(entries: &[(String, bool)]) -> Result<String, PluginError> {
+    entries
+        .iter()
+        .map(|(name, active)| {
+            if name.contains(['\r', '\n']) {
+                return Err(PluginError::Invalid(format!(
+                    "plugin name contains a line break: {:?}",
+                    name
+                )));
+            }
+            Ok(if *active {
+                format!("*{}\r\n", name)
+            } else {
+                format!("{}\r\n", name)
+            })
+        })
+        .collect()
+}
+
+/// Mod path adjustment for Bethesda (Gamebryo/Creation engine) games.
+///
+/// Legacy FOMod and OMod archives store their files relative to the game's
+/// `Data` folder, while NMM installs relative to the game root. This adds
+/// the missing `Data` prefix. Only the prefix itself and any explicit
+/// aliases for it count as already present; other stop folders such as
+/// `Textures` live inside `Data` and still get prefixed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn adjust_mod_path(&self, format_id: &str, path: &str, ignore_if_present: bool) -> String {
+///     BethesdaPathAdjuster::for_game(self).adjust(format_id, path, ignore_if_present)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BethesdaPathAdjuster {
+    prefix: String,
+    aliases: Vec<String>,
+}
+
+impl BethesdaPathAdjuster {
+    /// Format IDs whose paths are relative to the data folder.
+    pub const LEGACY_FORMATS: &'static [&'static str] = &["FOMod", "OMod"];
+
+    /// Create an adjuster that prepends the given folder.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Create an adjuster for a game.
+    ///
+    /// Prepends the game's first stop folder, or `Data` if it has none.
+    pub fn for_game(game: &(impl GameModeDescriptor + ?Sized)) -> Self {
+        Self::new(game.stop_folders().first().copied().unwrap_or("Data"))
+    }
+
+    /// Treat another folder name as the data folder, e.g. Morrowind's
+    /// `Data Files`.
+    pub fn with_alias(mut self, folder: impl Into<String>) -> Self {
+        self.aliases.push(folder.into());
+        self
+    }
+
+    /// Adjust a mod file path for the given format.
+    ///
+    /// Paths from formats other than [`LEGACY_FORMATS`](Self::LEGACY_FORMATS)
+    /// are returned unchanged. Otherwise separators are normalized to `/`
+    /// and the prefix is prepended. With `ignore_if_present`, a path that
+    /// already starts with the prefix or an alias (case-insensitive) is
+    /// left as is; without it the path is taken to be relative to the data
+    /// folder and is always prefixed.
+    pub fn adjust(&self, format_id: &str, path: &str, ignore_if_present: bool) -> String {
+        if !Self::LEGACY_FORMATS.contains(&format_id) {
+            return path.to_string();
+        }
+
+        let parts: Vec<&str> = path.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+        let normalized = parts.join("/");
+
+        let present = parts.first().is_some_and(|first| {
+            std::iter::once(&self.prefix)
+                .chain(&self.aliases)
+                .any(|folder| folder.eq_ignore_ascii_case(first))
+        });
+        if ignore_if_present && present {
+            return normalized;
+        }
+
+        format!("{}/{}", self.prefix, normalized)
+    }
+}
+
 /// Runtime game mode.
 ///
 /// Extends [`GameModeDescriptor`] with runtime information about a specific
@@ -186,6 +482,63 @@ pub trait GameMode: GameModeDescriptor {
     /// Whether this game uses a plugin system.
     fn uses_plugins(&self) -> bool;
 
+    /// Path to the game's plugin list file (e.g., `plugins.txt`).
+    ///
+    /// `None` for games without one.
+    fn plugins_file_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Read the load order from the plugin list file.
+    ///
+    /// Returns `(filename, active)` pairs in load order. A missing file,
+    /// or a game without a plugin list file, yields an empty order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::Io`] if the file exists but can't be read.
+    fn read_load_order_file(&self) -> Result<Vec<(String, bool)>, PluginError> {
+        let Some(path) = self.plugins_file_path() else {
+            return Ok(Vec::new());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(parse_plugins_txt(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the load order to the plugin list file.
+    ///
+    /// The list is written to a uniquely named temporary file in the same
+    /// directory, synced to disk and renamed into place, so neither the
+    /// game nor a concurrent writer ever sees a partially written list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::NoPluginListFile`] if the game has no plugin
+    /// list file, [`PluginError::Invalid`] if a name can't be written (see
+    /// [`format_plugins_txt`]), or [`PluginError::Io`] if the write fails.
+    fn write_load_order_file(&self, entries: &[(String, bool)]) -> Result<(), PluginError> {
+        let path = self
+            .plugins_file_path()
+            .ok_or(PluginError::NoPluginListFile)?;
+        let content = format_plugins_txt(entries)?;
+
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(dir)?;
+
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        std::io::Write::write_all(&mut tmp, content.as_bytes())?;
+        tmp.as_file().sync_all()?;
+        tmp.persist(&path).map_err(|e| e.error)?;
+        Ok(())
+    }
+
     /// Whether plugin auto-sorting is supported.
     fn supports_plugin_auto_sorting(&self) -> bool {
         false
@@ -207,15 +560,70 @@ pub trait GameMode: GameModeDescriptor {
             .any(|p| p.eq_ignore_ascii_case(plugin_name))
     }
 
+    /// Check that every plugin's masters are present in `plugins`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::MissingMaster`] for the first master that
+    /// isn't in the list.
+    fn validate_masters(&self, plugins: &[Plugin]) -> Result<(), PluginError> {
+        let available: HashSet<String> =
+            plugins.iter().map(|p| p.filename.to_lowercase()).collect();
+        for plugin in plugins {
+            if let Some(missing) = missing_masters_lowercase(plugin, &available)
+                .into_iter()
+                .next()
+            {
+                return Err(PluginError::MissingMaster(missing));
+            }
+        }
+        Ok(())
+    }
+
     /// Adjust path for mod format compatibility.
     ///
     /// This handles legacy mods that assume different installation paths.
     /// For example, older FOMods for Bethesda games might not include
-    /// the "Data" prefix.
+    /// the "Data" prefix; Bethesda game modes can delegate to
+    /// [`BethesdaPathAdjuster`].
     fn adjust_mod_path(&self, _format_id: &str, path: &str, _ignore_if_present: bool) -> String {
         path.to_string()
     }
 
+    /// Check whether a mod file exists in the game directory.
+    ///
+    /// `relative_path` is relative to the installation path, as recorded in
+    /// the install log (e.g., `Data/textures/sky.dds`). Either `/` or `\`
+    /// may be used as the separator. Combined with the install log, this
+    /// detects files that were removed outside of NMM.
+    ///
+    /// Returns `false` for paths that don't name a file inside the game
+    /// directory: empty paths, absolute paths and paths containing `..`.
+    fn is_mod_file_installed(&self, relative_path: &str) -> bool {
+        relative_game_path(relative_path)
+            .is_some_and(|relative| self.installation_path().join(relative).exists())
+    }
+
+    /// Find a game file in the plugin directory or the secondary install.
+    ///
+    /// `relative` is relative to the [`plugin_directory`](Self::plugin_directory),
+    /// which is checked first, then the
+    /// [`secondary_installation_path`](Self::secondary_installation_path).
+    /// Either `/` or `\` may be used as the separator. Returns the first
+    /// existing path, or `None` if neither has the file or `relative` is
+    /// empty, absolute or contains `..`.
+    fn resolve_game_file(&self, relative: &str) -> Option<PathBuf> {
+        let relative = relative_game_path(relative)?;
+        let primary = self.plugin_directory().join(&relative);
+        if primary.exists() {
+            return Some(primary);
+        }
+
+        self.secondary_installation_path()
+            .map(|secondary| secondary.join(&relative))
+            .filter(|path| path.exists())
+    }
+
     /// File extensions that require hardlinks instead of symlinks.
     ///
     /// Some file types (like Bethesda plugins) don't work correctly
@@ -224,15 +632,231 @@ pub trait GameMode: GameModeDescriptor {
         HashSet::new()
     }
 
+    /// Check if a file must be hardlinked rather than symlinked.
+    ///
+    /// Compares the file's extension case-insensitively against
+    /// [`hardlink_required_extensions`](Self::hardlink_required_extensions).
+    fn hardlink_required(&self, file_path: &str) -> bool {
+        let Some(ext) = Path::new(file_path).extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+        self.hardlink_required_extensions()
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    }
+
+    /// Decide how a mod file should be linked into the game directory.
+    fn link_strategy(&self, file_path: &str) -> LinkStrategy {
+        if self.hardlink_required(file_path) {
+            LinkStrategy::Hardlink
+        } else {
+            LinkStrategy::Symlink
+        }
+    }
+
     /// Get paths to game settings/INI files.
+    ///
+    /// Implementations can use [`settings_files_in`](Self::settings_files_in)
+    /// to list the known INI files that actually exist.
     fn settings_files(&self) -> Vec<PathBuf> {
         vec![]
     }
 
+    /// Find which of the named settings files exist in a directory.
+    ///
+    /// Returns `dir.join(name)` for each name that exists, in the order given.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// fn settings_files(&self) -> Vec<PathBuf> {
+    ///     self.settings_files_in(&self.settings_dir, &["Skyrim.ini", "SkyrimPrefs.ini"])
+    /// }
+    /// ```
+    fn settings_files_in(&self, dir: &Path, names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| dir.join(name))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
     /// Get the installed game version.
     fn game_version(&self) -> Option<semver::Version> {
         None
     }
+
+    /// Read the version from the game executable's version resource.
+    ///
+    /// Uses the first of [`game_executables`](GameModeDescriptor::game_executables)
+    /// found in the installation directory, so game modes can implement
+    /// [`game_version`](Self::game_version) by delegating here. The file
+    /// version `a.b.c.d` maps to `a.b.c`. Always `None` on platforms other
+    /// than Windows.
+    fn read_exe_version(&self) -> Option<semver::Version> {
+        #[cfg(windows)]
+        {
+            let exe = self
+                .game_executables()
+                .iter()
+                .map(|name| self.installation_path().join(name))
+                .find(|path| path.is_file())?;
+            exe_file_version(&exe)
+        }
+
+        #[cfg(not(windows))]
+        {
+            None
+        }
+    }
+}
+
+/// Read the file version from an executable's `VS_FIXEDFILEINFO` block.
+#[cfg(windows)]
+fn exe_file_version(path: &Path) -> Option<semver::Version> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::core::w;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    const SIGNATURE: u32 = 0xFEEF_04BD;
+
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    // SAFETY: `path` is NUL-terminated, `data` is sized by
+    // GetFileVersionInfoSizeW, and the pointer VerQueryValueW returns points
+    // into `data`, which is still alive when it's read. The read is
+    // unaligned in case the block isn't.
+    let info = unsafe {
+        let size = GetFileVersionInfoSizeW(path.as_ptr(), std::ptr::null_mut());
+        if size == 0 {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        if GetFileVersionInfoW(path.as_ptr(), 0, size, data.as_mut_ptr().cast()) == 0 {
+            return None;
+        }
+
+        let mut block = std::ptr::null_mut();
+        let mut len = 0u32;
+        if VerQueryValueW(data.as_ptr().cast(), w!("\\"), &mut block, &mut len) == 0
+            || block.is_null()
+            || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>()
+        {
+            return None;
+        }
+        std::ptr::read_unaligned(block.cast::<VS_FIXEDFILEINFO>())
+    };
+    if info.dwSignature != SIGNATURE {
+        return None;
+    }
+
+    Some(semver::Version::new(
+        u64::from(info.dwFileVersionMS >> 16),
+        u64::from(info.dwFileVersionMS & 0xFFFF),
+        u64::from(info.dwFileVersionLS >> 16),
+    ))
+}
+
+/// Creates [`GameMode`] instances for one game.
+pub trait GameModeFactory: Send + Sync {
+    /// Static metadata for the game this factory creates.
+    fn descriptor(&self) -> &dyn GameModeDescriptor;
+
+    /// Create a game mode for an installation directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameModeError`] if the directory isn't a usable install
+    /// of this game.
+    fn create(&self, install_path: PathBuf) -> Result<Box<dyn GameMode>, GameModeError>;
+}
+
+/// Registry of game mode factories.
+///
+/// Used to detect which game is installed in a directory and create the
+/// matching [`GameMode`].
+pub struct GameModeRegistry {
+    factories: Vec<Box<dyn GameModeFactory>>,
+}
+
+impl Default for GameModeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameModeRegistry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self {
+            factories: Vec::new(),
+        }
+    }
+
+    /// Register a game mode factory.
+    pub fn register(&mut self, factory: Box<dyn GameModeFactory>) {
+        self.factories.push(factory);
+    }
+
+    /// Find the factory for the game installed in a directory.
+    ///
+    /// Returns the first factory with one of its descriptor's
+    /// [`game_executables`](GameModeDescriptor::game_executables) in `path`.
+    pub fn detect(&self, path: &Path) -> Option<&dyn GameModeFactory> {
+        self.factories
+            .iter()
+            .find(|f| {
+                f.descriptor()
+                    .game_executables()
+                    .iter()
+                    .any(|exe| path.join(exe).is_file())
+            })
+            .map(|f| f.as_ref())
+    }
+
+    /// Get a factory by game mode ID.
+    pub fn get_factory(&self, mode_id: &str) -> Option<&dyn GameModeFactory> {
+        self.factories
+            .iter()
+            .find(|f| f.descriptor().mode_id() == mode_id)
+            .map(|f| f.as_ref())
+    }
+
+    /// Get all registered factories.
+    pub fn factories(&self) -> &[Box<dyn GameModeFactory>] {
+        &self.factories
+    }
+}
+
+/// Convert a logged mod file path into a platform path.
+///
+/// Logged paths may come from Windows and use `\` separators. Returns
+/// `None` unless the path names something strictly inside the directory
+/// it's joined to: empty paths, absolute paths (including drive and UNC
+/// prefixes) and `..` components are rejected. `.` components are dropped.
+/// Other colons are allowed, since they're valid in Unix file names.
+fn relative_game_path(path: &str) -> Option<PathBuf> {
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if drive || path.starts_with(['/', '\\']) {
+        return None;
+    }
+
+    let mut relative = PathBuf::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => return None,
+            _ => relative.push(part),
+        }
+    }
+
+    // On Windows a later part like "C:x" still parses as a prefix.
+    let normal = relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    (normal && !relative.as_os_str().is_empty()).then_some(relative)
 }
 
 #[cfg(test)]
@@ -274,4 +898,647 @@ mod tests {
         assert_eq!(desc.max_active_plugins(), 0);
         assert!(desc.required_tool_name().is_none());
     }
+
+    struct DlcDescriptor;
+
+    impl GameModeDescriptor for DlcDescriptor {
+        fn mode_id(&self) -> &str {
+            "SkyrimSE"
+        }
+        fn name(&self) -> &str {
+            "Skyrim Special Edition"
+        }
+        fn game_executables(&self) -> &[&str] {
+            &["SkyrimSE.exe"]
+        }
+        fn plugin_extensions(&self) -> &[&str] {
+            &[".esp", ".esm", ".esl"]
+        }
+        fn critical_plugins(&self) -> &[&str] {
+            &["Skyrim.esm", "Update.esm"]
+        }
+        fn official_plugins(&self) -> &[&str] {
+            &["Dawnguard.esm", "HearthFires.esm", "Dragonborn.esm"]
+        }
+        fn stop_folders(&self) -> &[&str] {
+            &[
+                "Data",
+                "Textures",
+                "Meshes",
+                "Sound",
+                "Scripts",
+                "Interface",
+                "SKSE",
+            ]
+        }
+        fn theme(&self) -> GameTheme {
+            GameTheme::default()
+        }
+    }
+
+    #[test]
+    fn test_official_plugin_index() {
+        let desc = DlcDescriptor;
+        assert_eq!(desc.official_plugin_index("Dawnguard.esm"), Some(0));
+        assert_eq!(desc.official_plugin_index("hearthfires.esm"), Some(1));
+        assert_eq!(desc.official_plugin_index("DRAGONBORN.ESM"), Some(2));
+        assert_eq!(desc.official_plugin_index("Skyrim.esm"), None);
+        assert_eq!(desc.official_plugin_index("MyMod.esp"), None);
+
+        assert!(desc.is_official_plugin("dawnguard.esm"));
+        assert!(!desc.is_official_plugin("MyMod.esp"));
+        assert!(!MockGameDescriptor.is_official_plugin("Dawnguard.esm"));
+    }
+
+    struct MockGameMode {
+        installation_path: PathBuf,
+        secondary_installation_path: Option<PathBuf>,
+        has_plugins_file: bool,
+    }
+
+    impl MockGameMode {
+        fn new(installation_path: impl Into<PathBuf>) -> Self {
+            Self {
+                installation_path: installation_path.into(),
+                secondary_installation_path: None,
+                has_plugins_file: true,
+            }
+        }
+    }
+
+    impl GameModeDescriptor for MockGameMode {
+        fn mode_id(&self) -> &str {
+            MockGameDescriptor.mode_id()
+        }
+        fn name(&self) -> &str {
+            MockGameDescriptor.name()
+        }
+        fn game_executables(&self) -> &[&str] {
+            &["MockGame.exe"]
+        }
+        fn plugin_extensions(&self) -> &[&str] {
+            &[".esp", ".esm"]
+        }
+        fn critical_plugins(&self) -> &[&str] {
+            &["Mock.esm"]
+        }
+        fn official_plugins(&self) -> &[&str] {
+            &["Mock.esm"]
+        }
+        fn stop_folders(&self) -> &[&str] {
+            &["Data"]
+        }
+        fn theme(&self) -> GameTheme {
+            GameTheme::default()
+        }
+    }
+
+    impl GameMode for MockGameMode {
+        fn installation_path(&self) -> &Path {
+            &self.installation_path
+        }
+        fn secondary_installation_path(&self) -> Option<&Path> {
+            self.secondary_installation_path.as_deref()
+        }
+        fn plugin_directory(&self) -> PathBuf {
+            self.installation_path.join("Data")
+        }
+        fn uses_plugins(&self) -> bool {
+            true
+        }
+        fn plugin_factory(&self) -> Option<Box<dyn PluginFactory>> {
+            None
+        }
+        fn plugin_order_validator(&self) -> Option<Box<dyn PluginOrderValidator>> {
+            None
+        }
+        fn load_order_manager(&self) -> Option<Box<dyn LoadOrderManager>> {
+            None
+        }
+        fn hardlink_required_extensions(&self) -> HashSet<&str> {
+            [".esp", ".esm", ".bsa"].into_iter().collect()
+        }
+        fn plugins_file_path(&self) -> Option<PathBuf> {
+            self.has_plugins_file
+                .then(|| self.installation_path.join("AppData").join("plugins.txt"))
+        }
+    }
+
+    struct MockFactory<D> {
+        descriptor: D,
+    }
+
+    impl<D: GameModeDescriptor> GameModeFactory for MockFactory<D> {
+        fn descriptor(&self) -> &dyn GameModeDescriptor {
+            &self.descriptor
+        }
+        fn create(&self, install_path: PathBuf) -> Result<Box<dyn GameMode>, GameModeError> {
+            if !install_path.is_dir() {
+                return Err(GameModeError::GameNotFound(install_path));
+            }
+            Ok(Box::new(MockGameMode::new(install_path)))
+        }
+    }
+
+    fn game_registry() -> GameModeRegistry {
+        let mut registry = GameModeRegistry::new();
+        registry.register(Box::new(MockFactory {
+            descriptor: MockGameDescriptor,
+        }));
+        registry.register(Box::new(MockFactory {
+            descriptor: DlcDescriptor,
+        }));
+        registry
+    }
+
+    #[test]
+    fn test_game_mode_registry_detect() {
+        let registry = game_registry();
+        let dir = tempfile::tempdir().unwrap();
+        assert!(registry.detect(dir.path()).is_none());
+
+        std::fs::write(dir.path().join("SkyrimSE.exe"), b"MZ").unwrap();
+        let factory = registry.detect(dir.path()).unwrap();
+        assert_eq!(factory.descriptor().mode_id(), "SkyrimSE");
+
+        let game = factory.create(dir.path().to_path_buf()).unwrap();
+        assert_eq!(game.installation_path(), dir.path());
+    }
+
+    #[test]
+    fn test_game_mode_registry_get_factory() {
+        let registry = game_registry();
+        assert_eq!(registry.factories().len(), 2);
+        assert!(registry.get_factory("MockGame").is_some());
+        assert!(registry.get_factory("Oblivion").is_none());
+
+        let missing = PathBuf::from("/nonexistent/MockGame");
+        assert!(matches!(
+            registry.get_factory("MockGame").unwrap().create(missing),
+            Err(GameModeError::GameNotFound(_))
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_read_exe_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = MockGameMode::new(dir.path());
+        assert!(game.read_exe_version().is_none());
+
+        // kernel32.dll is versioned 10.0.<build>.<revision> on Windows 10
+        // and 11.
+        let system_root = std::env::var_os("SystemRoot").unwrap();
+        std::fs::copy(
+            Path::new(&system_root)
+                .join("System32")
+                .join("kernel32.dll"),
+            dir.path().join("MockGame.exe"),
+        )
+        .unwrap();
+        let version = game.read_exe_version().unwrap();
+        assert_eq!((version.major, version.minor), (10, 0));
+        assert!(version.patch >= 10240);
+
+        // A binary without a version resource.
+        std::fs::write(dir.path().join("MockGame.exe"), b"MZ").unwrap();
+        assert!(game.read_exe_version().is_none());
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_read_exe_version() {
+        assert!(MockGameMode::new("/games/mock")
+            .read_exe_version()
+            .is_none());
+    }
+
+    #[test]
+    fn test_plugins_txt_round_trip() {
+        let entries = vec![
+            ("Mock.esm".to_string(), true),
+            ("MyMod.esp".to_string(), false),
+            ("Other.esp".to_string(), true),
+        ];
+        let content = format_plugins_txt(&entries).unwrap();
+        assert_eq!(content, "*Mock.esm\r\nMyMod.esp\r\n*Other.esp\r\n");
+        assert_eq!(parse_plugins_txt(&content), entries);
+
+        assert_eq!(
+            parse_plugins_txt("*\r\n * \r\n*Mock.esm\r\n"),
+            vec![("Mock.esm".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_read_load_order_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = MockGameMode::new(dir.path());
+        std::fs::create_dir(dir.path().join("AppData")).unwrap();
+        std::fs::write(
+            dir.path().join("AppData").join("plugins.txt"),
+            "# This file is used by the game.\r\n*Mock.esm\r\nMyMod.esp\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            game.read_load_order_file().unwrap(),
+            vec![
+                ("Mock.esm".to_string(), true),
+                ("MyMod.esp".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_load_order_file_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = MockGameMode::new(dir.path());
+        assert!(game.read_load_order_file().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_write_load_order_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = MockGameMode::new(dir.path());
+        let entries = vec![
+            ("Mock.esm".to_string(), true),
+            ("MyMod.esp".to_string(), true),
+        ];
+
+        game.write_load_order_file(&entries).unwrap();
+        assert_eq!(game.read_load_order_file().unwrap(), entries);
+
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path().join("AppData"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec!["plugins.txt"]);
+    }
+
+    #[test]
+    fn test_write_load_order_file_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = MockGameMode::new(dir.path());
+
+        for name in [
+            "Evil.esp\r\n*Injected.esp",
+            "Split\nName.esp",
+            "Cr\rOnly.esp",
+        ] {
+            let entries = vec![(name.to_string(), true)];
+            assert!(matches!(
+                game.write_load_order_file(&entries),
+                Err(PluginError::Invalid(_))
+            ));
+        }
+        assert!(!dir.path().join("AppData").join("plugins.txt").exists());
+
+        game.has_plugins_file = false;
+        assert!(matches!(
+            game.write_load_order_file(&[("Mock.esm".to_string(), true)]),
+            Err(PluginError::NoPluginListFile)
+        ));
+    }
+
+    #[test]
+    fn test_write_load_order_file_concurrent() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = MockGameMode::new(dir.path());
+        let orders: Vec<Vec<(String, bool)>> = (0..8)
+            .map(|i| {
+                vec![
+                    ("Mock.esm".to_string(), true),
+                    (format!("Writer{}.esp", i), i % 2 == 0),
+                ]
+            })
+            .collect();
+
+        std::thread::scope(|scope| {
+            for order in &orders {
+                let game = &game;
+                scope.spawn(move || game.write_load_order_file(order).unwrap());
+            }
+        });
+
+        let result = game.read_load_order_file().unwrap();
+        assert!(orders.contains(&result));
+
+        let leftovers = std::fs::read_dir(dir.path().join("AppData"))
+            .unwrap()
+            .count();
+        assert_eq!(leftovers, 1);
+    }
+
+    #[test]
+    fn test_is_plugin_extension() {
+        let game = MockGameMode::new("/games/mock");
+        assert!(game.is_plugin_extension("MyMod.esp"));
+        assert!(game.is_plugin_extension("Data/MyMod.ESP"));
+        assert!(game.is_plugin_extension("Master.Esm"));
+        assert!(!game.is_plugin_extension("textures/sky.dds"));
+        assert!(!game.is_plugin_extension("esp"));
+
+        assert!(!MockGameDescriptor.is_plugin_extension("MyMod.esp"));
+    }
+
+    #[test]
+    fn test_plugin_extension_set() {
+        let game = MockGameMode::new("/games/mock");
+        let set = game.plugin_extension_set();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains("esp"));
+        assert!(set.contains("esm"));
+    }
+
+    const PNG_BYTES: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+    #[test]
+    fn test_icon_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let icon = dir.path().join("icon.png");
+        std::fs::write(&icon, PNG_BYTES).unwrap();
+
+        let theme = GameTheme {
+            primary_color: "#2b5797".into(),
+            icon_path: Some(icon),
+        };
+        assert_eq!(theme.icon_bytes().unwrap(), Some(PNG_BYTES.to_vec()));
+        assert_eq!(theme.icon_format().unwrap(), Some(IconFormat::Png));
+    }
+
+    #[test]
+    fn test_icon_bytes_unset() {
+        let theme = GameTheme::default();
+        assert!(theme.icon_bytes().unwrap().is_none());
+        assert!(theme.icon_format().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_icon_bytes_missing_file() {
+        let theme = GameTheme {
+            primary_color: "#2b5797".into(),
+            icon_path: Some(PathBuf::from("/nonexistent/icon.png")),
+        };
+        assert!(theme.icon_bytes().is_err());
+        assert!(theme.icon_format().is_err());
+    }
+
+    #[test]
+    fn test_icon_format_sniff() {
+        assert_eq!(IconFormat::sniff(PNG_BYTES), Some(IconFormat::Png));
+        assert_eq!(
+            IconFormat::sniff(&PNG_BYTES[..IconFormat::HEADER_LEN]),
+            Some(IconFormat::Png)
+        );
+        assert_eq!(
+            IconFormat::sniff(b"\xff\xd8\xff\xe0"),
+            Some(IconFormat::Jpeg)
+        );
+        assert_eq!(IconFormat::sniff(b"GIF89a..."), Some(IconFormat::Gif));
+        assert_eq!(IconFormat::sniff(b"DDS "), None);
+        assert_eq!(IconFormat::sniff(b""), None);
+    }
+
+    #[test]
+    fn test_settings_files_in() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Mock.ini"), "[General]\n").unwrap();
+
+        let game = MockGameMode::new(dir.path());
+        let files = game.settings_files_in(dir.path(), &["Mock.ini", "MockPrefs.ini"]);
+        assert_eq!(files, vec![dir.path().join("Mock.ini")]);
+    }
+
+    #[test]
+    fn test_is_mod_file_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let textures = dir.path().join("Data").join("textures");
+        std::fs::create_dir_all(&textures).unwrap();
+        std::fs::write(textures.join("sky.dds"), b"DDS ").unwrap();
+
+        let game = MockGameMode::new(dir.path());
+        assert!(game.is_mod_file_installed("Data/textures/sky.dds"));
+        assert!(game.is_mod_file_installed("Data\\textures\\sky.dds"));
+
+        std::fs::remove_file(textures.join("sky.dds")).unwrap();
+        assert!(!game.is_mod_file_installed("Data/textures/sky.dds"));
+    }
+
+    #[test]
+    fn test_is_mod_file_installed_rejects_escaping_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path().join("game");
+        std::fs::create_dir_all(game_dir.join("Data")).unwrap();
+        std::fs::write(dir.path().join("outside.txt"), b"secret").unwrap();
+
+        let game = MockGameMode::new(&game_dir);
+        for path in [
+            "",
+            ".",
+            "..",
+            "/",
+            "../outside.txt",
+            "Data/../../outside.txt",
+        ] {
+            assert!(!game.is_mod_file_installed(path), "{:?}", path);
+        }
+
+        let absolute = dir.path().join("outside.txt");
+        assert!(!game.is_mod_file_installed(absolute.to_str().unwrap()));
+        assert!(!game.is_mod_file_installed("C:\\Windows\\win.ini"));
+        assert!(!game.is_mod_file_installed("C:Windows\\win.ini"));
+        assert!(!game.is_mod_file_installed("c:/Windows/win.ini"));
+        assert!(!game.is_mod_file_installed("\\\\server\\share\\file"));
+
+        assert!(game.is_mod_file_installed("./Data"));
+        assert_eq!(game.resolve_game_file(""), None);
+        assert_eq!(game.resolve_game_file(".."), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_is_mod_file_installed_colon_in_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path().join("Data");
+        std::fs::create_dir_all(&data).unwrap();
+        std::fs::write(data.join("notes: v2.txt"), b"").unwrap();
+
+        let game = MockGameMode::new(dir.path());
+        assert!(game.is_mod_file_installed("Data/notes: v2.txt"));
+        assert!(game.is_mod_file_installed("Data\\notes: v2.txt"));
+    }
+
+    #[test]
+    fn test_resolve_game_file() {
+        let primary = tempfile::tempdir().unwrap();
+        let secondary = tempfile::tempdir().unwrap();
+        std::fs::create_dir(primary.path().join("Data")).unwrap();
+        std::fs::write(primary.path().join("Data").join("Mock.esm"), b"TES4").unwrap();
+        std::fs::write(secondary.path().join("Mock.esm"), b"TES4").unwrap();
+        std::fs::create_dir(secondary.path().join("textures")).unwrap();
+        std::fs::write(secondary.path().join("textures").join("sky.dds"), b"DDS ").unwrap();
+
+        let mut game = MockGameMode::new(primary.path());
+        assert_eq!(game.resolve_game_file("textures/sky.dds"), None);
+
+        game.secondary_installation_path = Some(secondary.path().to_path_buf());
+        assert_eq!(
+            game.resolve_game_file("Mock.esm"),
+            Some(primary.path().join("Data").join("Mock.esm"))
+        );
+        assert_eq!(
+            game.resolve_game_file("textures\\sky.dds"),
+            Some(secondary.path().join("textures").join("sky.dds"))
+        );
+        assert_eq!(game.resolve_game_file("Missing.esp"), None);
+    }
+
+    fn plugin(filename: &str, masters: &[&str]) -> Plugin {
+        Plugin {
+            path: PathBuf::from("Data").join(filename),
+            filename: filename.to_string(),
+            is_master: filename.ends_with(".esm"),
+            is_light: false,
+            masters: masters.iter().map(|m| m.to_string()).collect(),
+            description: None,
+            author: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_masters() {
+        let plugin = plugin("MyMod.esp", &["Mock.esm", "MockDLC.esm"]);
+
+        let satisfied: HashSet<String> = ["mock.esm", "mockdlc.esm"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(missing_masters(&plugin, &satisfied).is_empty());
+
+        let partial: HashSet<String> = ["mock.esm".to_string()].into_iter().collect();
+        assert_eq!(missing_masters(&plugin, &partial), vec!["MockDLC.esm"]);
+
+        let mixed_case: HashSet<String> = ["MOCK.ESM", "MockDlc.esm"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(missing_masters(&plugin, &mixed_case).is_empty());
+
+        let partial: HashSet<String> = ["Mock.esm".to_string()].into_iter().collect();
+        assert_eq!(missing_masters(&plugin, &partial), vec!["MockDLC.esm"]);
+    }
+
+    #[test]
+    fn test_validate_masters() {
+        let game = MockGameMode::new("/games/mock");
+
+        let plugins = vec![plugin("Mock.esm", &[]), plugin("MyMod.esp", &["Mock.esm"])];
+        assert!(game.validate_masters(&plugins).is_ok());
+
+        let plugins = vec![plugin("MOCK.ESM", &[]), plugin("MyMod.esp", &["Mock.esm"])];
+        assert!(game.validate_masters(&plugins).is_ok());
+
+        let plugins = vec![
+            plugin("Mock.esm", &[]),
+            plugin("MyMod.esp", &["Mock.esm", "MockDLC.esm"]),
+        ];
+        assert!(matches!(
+            game.validate_masters(&plugins),
+            Err(PluginError::MissingMaster(ref m)) if m == "MockDLC.esm"
+        ));
+    }
+
+    #[test]
+    fn test_bethesda_path_adjuster() {
+        let game = MockGameMode::new("/games/mock");
+        let adjuster = BethesdaPathAdjuster::for_game(&game);
+
+        assert_eq!(
+            adjuster.adjust("FOMod", "Data/textures/x.dds", true),
+            "Data/textures/x.dds"
+        );
+        assert_eq!(
+            adjuster.adjust("OMod", "data\\textures\\x.dds", true),
+            "data/textures/x.dds"
+        );
+        assert_eq!(
+            adjuster.adjust("FOMod", "textures\\x.dds", false),
+            "Data/textures/x.dds"
+        );
+        assert_eq!(
+            adjuster.adjust("FOMod", "textures/x.dds", true),
+            "Data/textures/x.dds"
+        );
+        assert_eq!(adjuster.adjust("FOMod", "/x.esp", true), "Data/x.esp");
+    }
+
+    #[test]
+    fn test_bethesda_path_adjuster_ignore_if_present() {
+        let adjuster = BethesdaPathAdjuster::new("Data");
+
+        assert_eq!(adjuster.adjust("FOMod", "Data/x.esp", true), "Data/x.esp");
+        assert_eq!(
+            adjuster.adjust("FOMod", "Data/x.esp", false),
+            "Data/Data/x.esp"
+        );
+    }
+
+    #[test]
+    fn test_bethesda_path_adjuster_skyrim_stop_folders() {
+        let adjuster = BethesdaPathAdjuster::for_game(&DlcDescriptor);
+
+        for ignore_if_present in [true, false] {
+            assert_eq!(
+                adjuster.adjust("FOMod", "textures/x.dds", ignore_if_present),
+                "Data/textures/x.dds"
+            );
+            assert_eq!(
+                adjuster.adjust("FOMod", "SKSE\\Plugins\\x.dll", ignore_if_present),
+                "Data/SKSE/Plugins/x.dll"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bethesda_path_adjuster_alias() {
+        let adjuster = BethesdaPathAdjuster::new("Data").with_alias("Data Files");
+
+        assert_eq!(
+            adjuster.adjust("OMod", "data files\\x.esp", true),
+            "data files/x.esp"
+        );
+        assert_eq!(
+            adjuster.adjust("OMod", "textures/x.dds", true),
+            "Data/textures/x.dds"
+        );
+    }
+
+    #[test]
+    fn test_bethesda_path_adjuster_other_formats() {
+        let adjuster = BethesdaPathAdjuster::new("Data");
+        assert_eq!(
+            adjuster.adjust("Generic", "textures/x.dds", false),
+            "textures/x.dds"
+        );
+    }
+
+    #[test]
+    fn test_hardlink_required() {
+        let game = MockGameMode::new("/games/mock");
+        assert!(game.hardlink_required("Data/MyMod.esp"));
+        assert!(game.hardlink_required("Data/MyMod.ESP"));
+        assert!(!game.hardlink_required("Data/textures/sky.dds"));
+        assert!(!game.hardlink_required("Data/README"));
+    }
+
+    #[test]
+    fn test_link_strategy() {
+        let game = MockGameMode::new("/games/mock");
+        assert_eq!(game.link_strategy("Data/MyMod.esp"), LinkStrategy::Hardlink);
+        assert_eq!(
+            game.link_strategy("Data/textures/sky.dds"),
+            LinkStrategy::Symlink
+        );
+    }
 }
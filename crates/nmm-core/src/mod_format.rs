@@ -3,16 +3,25 @@
 //! This module defines the [`ModFormat`] trait for handling different
 //! mod archive formats like FOMod, OMod, and generic archives.
 
-use crate::error::ModFormatError;
+use crate::error::{ModFormatError, ParseFormatConfidenceError};
 use crate::game_mode::GameMode;
 use crate::mod_info::Mod;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
 
 /// Confidence level for format detection.
 ///
 /// When detecting what format a mod archive is in, different formats
 /// can claim different levels of confidence.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Serializes and displays as the variant name (e.g., `"Match"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FormatConfidence {
     /// The file is incompatible with this format.
     Incompatible = 0,
@@ -32,6 +41,106 @@ impl FormatConfidence {
     pub fn is_usable(&self) -> bool {
         *self >= FormatConfidence::Compatible
     }
+
+    /// The variant name, as used by `Display` and `FromStr`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FormatConfidence::Incompatible => "Incompatible",
+            FormatConfidence::Convertible => "Convertible",
+            FormatConfidence::Compatible => "Compatible",
+            FormatConfidence::Match => "Match",
+        }
+    }
+}
+
+impl fmt::Display for FormatConfidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FormatConfidence {
+    type Err = ParseFormatConfidenceError;
+
+    /// Parse a variant name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            FormatConfidence::Incompatible,
+            FormatConfidence::Convertible,
+            FormatConfidence::Compatible,
+            FormatConfidence::Match,
+        ]
+        .into_iter()
+        .find(|c| c.as_str().eq_ignore_ascii_case(s.trim()))
+        .ok_or_else(|| ParseFormatConfidenceError(s.to_string()))
+    }
+}
+
+/// Compression level used when creating mod archives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionLevel {
+    /// Store files without compression.
+    Store,
+
+    /// Favor speed over size.
+    Fast,
+
+    /// The format's default balance of speed and size.
+    #[default]
+    Default,
+
+    /// Favor size over speed.
+    Best,
+}
+
+/// Validated mod format identifier.
+///
+/// Non-empty and free of whitespace, so IDs used for lookup and
+/// persistence compare reliably.
+///
+/// # Example
+///
+/// ```rust
+/// use nmm_core::FormatId;
+///
+/// assert_eq!(FormatId::new("FOMod").unwrap().as_str(), "FOMod");
+/// assert!(FormatId::new("").is_err());
+/// assert!(FormatId::new("Generic Archive").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FormatId(String);
+
+impl FormatId {
+    /// Validate and wrap a format ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModFormatError::InvalidId`] if `id` is empty or contains
+    /// whitespace.
+    pub fn new(id: impl Into<String>) -> Result<Self, ModFormatError> {
+        let id = id.into();
+        if id.is_empty() || id.chars().any(char::is_whitespace) {
+            return Err(ModFormatError::InvalidId(id));
+        }
+        Ok(Self(id))
+    }
+
+    /// Get the ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for FormatId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for FormatId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 /// Mod archive format handler.
@@ -80,9 +189,47 @@ pub trait ModFormat: Send + Sync {
     /// Used for format matching and persistence.
     fn id(&self) -> &str;
 
+    /// The validated form of [`id`](Self::id).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModFormatError::InvalidId`] if the ID is empty or
+    /// contains whitespace.
+    fn validated_id(&self) -> Result<FormatId, ModFormatError> {
+        FormatId::new(self.id())
+    }
+
     /// File extension typically used for this format.
     fn extension(&self) -> &str;
 
+    /// All file extensions handled by this format.
+    ///
+    /// Formats that accept several archive types (e.g., `.zip`, `.7z`
+    /// and `.rar`) override this. The default returns just
+    /// [`extension`](Self::extension).
+    fn extensions(&self) -> Vec<&str> {
+        vec![self.extension()]
+    }
+
+    /// Cheaply rule out a file by its extension alone.
+    ///
+    /// Returns `true` when the path's extension matches none of
+    /// [`extensions`](Self::extensions), compared case-insensitively.
+    /// A format with an empty extension never rejects a file.
+    fn quick_reject(&self, path: &Path) -> bool {
+        let extensions = self.extensions();
+        if extensions.iter().any(|e| e.is_empty()) {
+            return false;
+        }
+
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            return true;
+        };
+        !extensions
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    }
+
     /// Whether this format supports creating/compressing mods.
     fn supports_compression(&self) -> bool;
 
@@ -97,6 +244,29 @@ pub trait ModFormat: Send + Sync {
     /// A [`FormatConfidence`] indicating how well the file matches.
     fn check_compliance(&self, path: &Path) -> FormatConfidence;
 
+    /// Check an archive's structure, explaining any problem found.
+    ///
+    /// Where [`check_compliance`](Self::check_compliance) only reports a
+    /// confidence, this reports *why* an archive is malformed, for import
+    /// diagnostics. The default implementation accepts everything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModFormatError::CorruptArchive`] describing the problem,
+    /// e.g. `"missing fomod/ModuleConfig.xml"`.
+    fn validate_archive(&self, _path: &Path) -> Result<(), ModFormatError> {
+        Ok(())
+    }
+
+    /// Check whether mods in this format can be installed into a game.
+    ///
+    /// Formats tied to specific games (e.g., OMod for Bethesda games)
+    /// override this; `create_mod` implementations may check it up front.
+    /// The default accepts every game.
+    fn can_install_to(&self, _game_mode: &dyn GameMode) -> bool {
+        true
+    }
+
     /// Create a [`Mod`] instance from an archive file.
     ///
     /// # Arguments
@@ -112,14 +282,39 @@ pub trait ModFormat: Send + Sync {
         path: &Path,
         game_mode: &dyn GameMode,
     ) -> Result<Box<dyn Mod>, ModFormatError>;
+
+    /// Create an archive in this format from in-memory files.
+    ///
+    /// Only formats that report [`supports_compression`](Self::supports_compression)
+    /// need to implement this.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - Archive paths paired with their contents
+    /// * `out` - Path of the archive to create
+    /// * `level` - Compression level to use
+    ///
+    /// # Errors
+    ///
+    /// The default implementation returns [`ModFormatError::UnsupportedFormat`].
+    fn create_archive(
+        &self,
+        _files: &[(String, Vec<u8>)],
+        _out: &Path,
+        _level: CompressionLevel,
+    ) -> Result<(), ModFormatError> {
+        Err(ModFormatError::UnsupportedFormat)
+    }
 }
 
 /// Registry of mod formats.
 ///
 /// Used to detect the format of mod archives and create appropriate
-/// handlers.
+/// handlers. Formats are keyed by the [`FormatId`] validated when they
+/// were registered.
 pub struct ModFormatRegistry {
     formats: Vec<Box<dyn ModFormat>>,
+    index: HashMap<FormatId, usize>,
 }
 
 impl Default for ModFormatRegistry {
@@ -133,12 +328,51 @@ impl ModFormatRegistry {
     pub fn new() -> Self {
         Self {
             formats: Vec::new(),
+            index: HashMap::new(),
         }
     }
 
     /// Register a format handler.
-    pub fn register(&mut self, format: Box<dyn ModFormat>) {
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModFormatError::InvalidId`] if the format's ID is empty or
+    /// contains whitespace, or [`ModFormatError::DuplicateId`] if a format
+    /// with the same ID is already registered.
+    pub fn register(&mut self, format: Box<dyn ModFormat>) -> Result<(), ModFormatError> {
+        let id = format.validated_id()?;
+        if self.index.contains_key(&id) {
+            return Err(ModFormatError::DuplicateId(id.to_string()));
+        }
+        self.index.insert(id, self.formats.len());
         self.formats.push(format);
+        Ok(())
+    }
+
+    /// Remove the format with the given ID.
+    ///
+    /// Returns `true` if a format was removed.
+    pub fn unregister<Q>(&mut self, id: &Q) -> bool
+    where
+        FormatId: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(removed) = self.index.remove(id) else {
+            return false;
+        };
+        self.formats.remove(removed);
+        for position in self.index.values_mut() {
+            if *position > removed {
+                *position -= 1;
+            }
+        }
+        true
+    }
+
+    /// Remove all registered formats.
+    pub fn clear(&mut self) {
+        self.formats.clear();
+        self.index.clear();
     }
 
     /// Detect the best matching format for a file.
@@ -153,9 +387,71 @@ impl ModFormatRegistry {
             .map(|(f, _)| f)
     }
 
-    /// Get a format by ID.
-    pub fn get_format(&self, id: &str) -> Option<&dyn ModFormat> {
-        self.formats.iter().find(|f| f.id() == id).map(|f| f.as_ref())
+    /// Formats not ruled out for a file by [`ModFormat::quick_reject`].
+    ///
+    /// Useful for narrowing the candidates before running the more
+    /// expensive [`ModFormat::check_compliance`].
+    pub fn candidate_formats(&self, path: &Path) -> Vec<&dyn ModFormat> {
+        self.formats
+            .iter()
+            .map(|f| f.as_ref())
+            .filter(|f| !f.quick_reject(path))
+            .collect()
+    }
+
+    /// Detect a file's format, reusing a cached result when possible.
+    ///
+    /// The cached result is used while the file's size and modification
+    /// time are unchanged; otherwise the file is re-detected and the cache
+    /// updated. Only successful detections are cached, so a file no format
+    /// recognised is sniffed again once more formats are registered. Files
+    /// whose metadata can't be read are detected without caching.
+    pub fn detect_format_cached(
+        &self,
+        path: &Path,
+        cache: &mut DetectionCache,
+    ) -> Option<&dyn ModFormat> {
+        let Some((modified, size)) = std::fs::metadata(path)
+            .ok()
+            .and_then(|m| Some((m.modified().ok()?, m.len())))
+        else {
+            return self.detect_format(path);
+        };
+
+        if let Some(entry) = cache.entries.get(path) {
+            if entry.modified == modified && entry.size == size {
+                if let Some(format) = self.get_format(entry.format_id.as_str()) {
+                    return Some(format);
+                }
+            }
+        }
+
+        let format = self.detect_format(path);
+        match format {
+            Some(f) => {
+                cache.entries.insert(
+                    path.to_path_buf(),
+                    DetectionCacheEntry {
+                        modified,
+                        size,
+                        format_id: f.id().to_string(),
+                    },
+                );
+            }
+            None => {
+                cache.entries.remove(path);
+            }
+        }
+        format
+    }
+
+    /// Get a format by ID, given as a [`FormatId`] or a `&str`.
+    pub fn get_format<Q>(&self, id: &Q) -> Option<&dyn ModFormat>
+    where
+        FormatId: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get(id).map(|&i| self.formats[i].as_ref())
     }
 
     /// Get all registered formats.
@@ -164,9 +460,727 @@ impl ModFormatRegistry {
     }
 }
 
+/// Cached format detection results, keyed by archive path.
+///
+/// Used with [`ModFormatRegistry::detect_format_cached`]. Serializable so
+/// hosts can persist it between runs.
+///
+/// The cache belongs to the caller; the registry only borrows it for a
+/// lookup. Entries are replaced when a file changes but never dropped on
+/// their own, so hosts should [`prune`](Self::prune) it when loading it
+/// or after a scan, and [`remove`](Self::remove) paths they delete.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectionCache {
+    entries: HashMap<PathBuf, DetectionCacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DetectionCacheEntry {
+    modified: SystemTime,
+    size: u64,
+    format_id: String,
+}
+
+impl DetectionCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Remove the cached result for a path. Returns whether it was cached.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        self.entries.remove(path).is_some()
+    }
+
+    /// Keep only the cached results whose path satisfies `keep`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Path) -> bool) {
+        self.entries.retain(|path, _| keep(path));
+    }
+
+    /// Drop results for files that are gone or have changed since they
+    /// were cached.
+    pub fn prune(&mut self) {
+        self.entries.retain(|path, entry| {
+            std::fs::metadata(path)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len())))
+                .is_some_and(|(modified, size)| modified == entry.modified && size == entry.size)
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game_mode::{
+        GameModeDescriptor, GameTheme, LoadOrderManager, PluginFactory, PluginOrderValidator,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    struct TestGame {
+        mode_id: &'static str,
+        installation_path: PathBuf,
+    }
+
+    impl TestGame {
+        fn new(mode_id: &'static str) -> Self {
+            Self {
+                mode_id,
+                installation_path: PathBuf::from("/games").join(mode_id),
+            }
+        }
+    }
+
+    impl GameModeDescriptor for TestGame {
+        fn mode_id(&self) -> &str {
+            self.mode_id
+        }
+        fn name(&self) -> &str {
+            self.mode_id
+        }
+        fn game_executables(&self) -> &[&str] {
+            &[]
+        }
+        fn plugin_extensions(&self) -> &[&str] {
+            &[]
+        }
+        fn critical_plugins(&self) -> &[&str] {
+            &[]
+        }
+        fn official_plugins(&self) -> &[&str] {
+            &[]
+        }
+        fn stop_folders(&self) -> &[&str] {
+            &["Data"]
+        }
+        fn theme(&self) -> GameTheme {
+            GameTheme::default()
+        }
+    }
+
+    impl GameMode for TestGame {
+        fn installation_path(&self) -> &Path {
+            &self.installation_path
+        }
+        fn plugin_directory(&self) -> PathBuf {
+            self.installation_path.join("Data")
+        }
+        fn uses_plugins(&self) -> bool {
+            false
+        }
+        fn plugin_factory(&self) -> Option<Box<dyn PluginFactory>> {
+            None
+        }
+        fn plugin_order_validator(&self) -> Option<Box<dyn PluginOrderValidator>> {
+            None
+        }
+        fn load_order_manager(&self) -> Option<Box<dyn LoadOrderManager>> {
+            None
+        }
+    }
+
+    /// Only installs into one game mode.
+    struct GameSpecificFormat {
+        mode_id: &'static str,
+    }
+
+    impl ModFormat for GameSpecificFormat {
+        fn name(&self) -> &str {
+            "Game Specific"
+        }
+        fn id(&self) -> &str {
+            "GameSpecific"
+        }
+        fn extension(&self) -> &str {
+            ".gs"
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            FormatConfidence::Compatible
+        }
+        fn can_install_to(&self, game_mode: &dyn GameMode) -> bool {
+            game_mode.mode_id() == self.mode_id
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            if !self.can_install_to(game_mode) {
+                return Err(ModFormatError::UnsupportedFormat);
+            }
+            Err(ModFormatError::CorruptArchive("not implemented".into()))
+        }
+    }
+
+    struct ReadOnlyFormat;
+
+    impl ModFormat for ReadOnlyFormat {
+        fn name(&self) -> &str {
+            "Read Only"
+        }
+        fn id(&self) -> &str {
+            "ReadOnly"
+        }
+        fn extension(&self) -> &str {
+            ".ro"
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            FormatConfidence::Compatible
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+    }
+
+    /// Writes a trivial length-prefixed archive and records the level used.
+    #[derive(Default)]
+    struct SpyCompressingFormat {
+        last_level: Mutex<Option<CompressionLevel>>,
+    }
+
+    impl ModFormat for SpyCompressingFormat {
+        fn name(&self) -> &str {
+            "Spy"
+        }
+        fn id(&self) -> &str {
+            "Spy"
+        }
+        fn extension(&self) -> &str {
+            ".spy"
+        }
+        fn supports_compression(&self) -> bool {
+            true
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            FormatConfidence::Compatible
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+        fn create_archive(
+            &self,
+            files: &[(String, Vec<u8>)],
+            out: &Path,
+            level: CompressionLevel,
+        ) -> Result<(), ModFormatError> {
+            *self.last_level.lock().unwrap() = Some(level);
+
+            let mut bytes = Vec::new();
+            for (name, data) in files {
+                bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(name.as_bytes());
+                bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(data);
+            }
+            std::fs::write(out, bytes)?;
+            Ok(())
+        }
+    }
+
+    /// Treats a directory as an archive that must contain a marker file.
+    struct MarkerFormat;
+
+    impl MarkerFormat {
+        const MARKER: &'static str = "fomod/ModuleConfig.xml";
+    }
+
+    impl ModFormat for MarkerFormat {
+        fn name(&self) -> &str {
+            "Marker"
+        }
+        fn id(&self) -> &str {
+            "Marker"
+        }
+        fn extension(&self) -> &str {
+            ""
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, path: &Path) -> FormatConfidence {
+            if path.join(Self::MARKER).exists() {
+                FormatConfidence::Match
+            } else {
+                FormatConfidence::Incompatible
+            }
+        }
+        fn validate_archive(&self, path: &Path) -> Result<(), ModFormatError> {
+            if path.join(Self::MARKER).exists() {
+                Ok(())
+            } else {
+                Err(ModFormatError::CorruptArchive(format!(
+                    "missing {}",
+                    Self::MARKER
+                )))
+            }
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+    }
+
+    /// Accepts several generic archive types.
+    struct GenericArchiveFormat;
+
+    impl ModFormat for GenericArchiveFormat {
+        fn name(&self) -> &str {
+            "Generic Archive"
+        }
+        fn id(&self) -> &str {
+            "Generic"
+        }
+        fn extension(&self) -> &str {
+            ".zip"
+        }
+        fn extensions(&self) -> Vec<&str> {
+            vec![".zip", ".7z", ".rar"]
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            FormatConfidence::Compatible
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+    }
+
+    #[test]
+    fn test_extensions() {
+        assert_eq!(
+            GenericArchiveFormat.extensions(),
+            vec![".zip", ".7z", ".rar"]
+        );
+        assert_eq!(ReadOnlyFormat.extensions(), vec![".ro"]);
+    }
+
+    #[test]
+    fn test_quick_reject() {
+        for path in ["mod.zip", "mod.7z", "mod.RAR"] {
+            assert!(
+                !GenericArchiveFormat.quick_reject(Path::new(path)),
+                "{}",
+                path
+            );
+        }
+        assert!(GenericArchiveFormat.quick_reject(Path::new("mod.ro")));
+        assert!(GenericArchiveFormat.quick_reject(Path::new("mod")));
+
+        assert!(!ReadOnlyFormat.quick_reject(Path::new("mod.ro")));
+        assert!(ReadOnlyFormat.quick_reject(Path::new("mod.7z")));
+
+        assert!(!MarkerFormat.quick_reject(Path::new("some/dir")));
+    }
+
+    #[test]
+    fn test_registry_candidate_formats() {
+        let mut registry = ModFormatRegistry::new();
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+        registry.register(Box::new(GenericArchiveFormat)).unwrap();
+
+        let ids = |path: &str| -> Vec<&str> {
+            registry
+                .candidate_formats(Path::new(path))
+                .iter()
+                .map(|f| f.id())
+                .collect()
+        };
+        assert_eq!(ids("mod.7z"), vec!["Generic"]);
+        assert_eq!(ids("mod.ro"), vec!["ReadOnly"]);
+        assert!(ids("mod.txt").is_empty());
+    }
+
+    /// Counts how often it's asked to sniff a file.
+    struct CountingFormat {
+        checks: Arc<AtomicUsize>,
+    }
+
+    impl ModFormat for CountingFormat {
+        fn name(&self) -> &str {
+            "Counting"
+        }
+        fn id(&self) -> &str {
+            "Counting"
+        }
+        fn extension(&self) -> &str {
+            ".7z"
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            self.checks.fetch_add(1, Ordering::SeqCst);
+            FormatConfidence::Compatible
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+    }
+
+    fn counting_registry() -> (ModFormatRegistry, Arc<AtomicUsize>) {
+        let checks = Arc::new(AtomicUsize::new(0));
+        let mut registry = ModFormatRegistry::new();
+        registry
+            .register(Box::new(CountingFormat {
+                checks: Arc::clone(&checks),
+            }))
+            .unwrap();
+        (registry, checks)
+    }
+
+    #[test]
+    fn test_detect_format_cached_hit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mod.7z");
+        std::fs::write(&path, b"7z archive").unwrap();
+        let (registry, checks) = counting_registry();
+        let mut cache = DetectionCache::new();
+
+        let first = registry.detect_format_cached(&path, &mut cache);
+        assert_eq!(first.map(|f| f.id()), Some("Counting"));
+        assert_eq!(cache.len(), 1);
+
+        // Survives a round trip through serde.
+        let json = serde_json::to_string(&cache).unwrap();
+        let mut cache: DetectionCache = serde_json::from_str(&json).unwrap();
+
+        let second = registry.detect_format_cached(&path, &mut cache);
+        assert_eq!(second.map(|f| f.id()), Some("Counting"));
+        assert_eq!(checks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_detect_format_cached_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mod.7z");
+        std::fs::write(&path, b"7z archive").unwrap();
+        let (registry, checks) = counting_registry();
+        let mut cache = DetectionCache::new();
+
+        registry.detect_format_cached(&path, &mut cache);
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        registry.detect_format_cached(&path, &mut cache);
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.len(), 1);
+
+        registry.detect_format_cached(&path, &mut cache);
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_detection_cache_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("kept.7z");
+        let deleted = dir.path().join("deleted.7z");
+        let replaced = dir.path().join("replaced.7z");
+        for path in [&kept, &deleted, &replaced] {
+            std::fs::write(path, b"7z archive").unwrap();
+        }
+        let (registry, _) = counting_registry();
+        let mut cache = DetectionCache::new();
+        for path in [&kept, &deleted, &replaced] {
+            registry.detect_format_cached(path, &mut cache);
+        }
+        assert_eq!(cache.len(), 3);
+
+        std::fs::remove_file(&deleted).unwrap();
+        std::fs::write(&replaced, b"a different, longer archive").unwrap();
+        cache.prune();
+        assert_eq!(cache.len(), 1);
+
+        assert!(!cache.remove(&deleted));
+        assert!(cache.remove(&kept));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_detection_cache_retain() {
+        let dir = tempfile::tempdir().unwrap();
+        let (registry, _) = counting_registry();
+        let mut cache = DetectionCache::new();
+        for name in ["a.7z", "b.7z"] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, b"7z archive").unwrap();
+            registry.detect_format_cached(&path, &mut cache);
+        }
+
+        cache.retain(|path| path.ends_with("a.7z"));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.remove(&dir.path().join("a.7z")));
+    }
+
+    #[test]
+    fn test_detect_format_cached_miss_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mod.7z");
+        std::fs::write(&path, b"7z archive").unwrap();
+        let mut registry = ModFormatRegistry::new();
+        let mut cache = DetectionCache::new();
+
+        assert!(registry.detect_format_cached(&path, &mut cache).is_none());
+        assert!(cache.is_empty());
+
+        let checks = Arc::new(AtomicUsize::new(0));
+        registry
+            .register(Box::new(CountingFormat {
+                checks: Arc::clone(&checks),
+            }))
+            .unwrap();
+
+        let found = registry.detect_format_cached(&path, &mut cache);
+        assert_eq!(found.map(|f| f.id()), Some("Counting"));
+        assert_eq!(checks.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_can_install_to() {
+        let format = GameSpecificFormat {
+            mode_id: "Oblivion",
+        };
+        let oblivion = TestGame::new("Oblivion");
+        let witcher = TestGame::new("Witcher3");
+
+        assert!(format.can_install_to(&oblivion));
+        assert!(!format.can_install_to(&witcher));
+        assert!(matches!(
+            format.create_mod(Path::new("mod.gs"), &witcher),
+            Err(ModFormatError::UnsupportedFormat)
+        ));
+    }
+
+    #[test]
+    fn test_can_install_to_default() {
+        assert!(ReadOnlyFormat.can_install_to(&TestGame::new("Witcher3")));
+    }
+
+    #[test]
+    fn test_validate_archive() {
+        let dir = tempfile::tempdir().unwrap();
+
+        match MarkerFormat.validate_archive(dir.path()) {
+            Err(ModFormatError::CorruptArchive(msg)) => {
+                assert_eq!(msg, "missing fomod/ModuleConfig.xml");
+            }
+            other => panic!("expected CorruptArchive, got {:?}", other),
+        }
+
+        std::fs::create_dir(dir.path().join("fomod")).unwrap();
+        std::fs::write(dir.path().join(MarkerFormat::MARKER), "<config/>").unwrap();
+        assert!(MarkerFormat.validate_archive(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_archive_default() {
+        assert!(ReadOnlyFormat
+            .validate_archive(Path::new("anything.7z"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_create_archive_honors_level() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("test.spy");
+        let format = SpyCompressingFormat::default();
+        let files = vec![("Data/test.esp".to_string(), b"TES4".to_vec())];
+
+        format
+            .create_archive(&files, &out, CompressionLevel::Best)
+            .unwrap();
+
+        assert!(out.exists());
+        assert_eq!(
+            *format.last_level.lock().unwrap(),
+            Some(CompressionLevel::Best)
+        );
+    }
+
+    #[test]
+    fn test_create_archive_unsupported_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("test.ro");
+
+        let result = ReadOnlyFormat.create_archive(&[], &out, CompressionLevel::default());
+        assert!(matches!(result, Err(ModFormatError::UnsupportedFormat)));
+        assert!(!out.exists());
+    }
+
+    /// A format with an arbitrary, possibly invalid, ID.
+    struct NamedFormat(&'static str);
+
+    impl ModFormat for NamedFormat {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn id(&self) -> &str {
+            self.0
+        }
+        fn extension(&self) -> &str {
+            ".named"
+        }
+        fn supports_compression(&self) -> bool {
+            false
+        }
+        fn check_compliance(&self, _path: &Path) -> FormatConfidence {
+            FormatConfidence::Compatible
+        }
+        fn create_mod(
+            &self,
+            _path: &Path,
+            _game_mode: &dyn GameMode,
+        ) -> Result<Box<dyn Mod>, ModFormatError> {
+            Err(ModFormatError::UnsupportedFormat)
+        }
+    }
+
+    #[test]
+    fn test_format_id() {
+        assert_eq!(ReadOnlyFormat.validated_id().unwrap().as_str(), "ReadOnly");
+        assert_eq!(FormatId::new("FOMod").unwrap().to_string(), "FOMod");
+
+        for id in ["", " ", "Read Only", "FOMod\n", "\tOMod"] {
+            assert!(
+                matches!(FormatId::new(id), Err(ModFormatError::InvalidId(ref s)) if s == id),
+                "{:?}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_registry_register_rejects_invalid_id() {
+        let mut registry = ModFormatRegistry::new();
+
+        for id in ["", "   ", "Generic Archive"] {
+            assert!(matches!(
+                registry.register(Box::new(NamedFormat(id))),
+                Err(ModFormatError::InvalidId(_))
+            ));
+        }
+        assert!(registry.formats().is_empty());
+    }
+
+    #[test]
+    fn test_registry_register_rejects_duplicate_id() {
+        let mut registry = ModFormatRegistry::new();
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+
+        assert!(matches!(
+            registry.register(Box::new(NamedFormat("ReadOnly"))),
+            Err(ModFormatError::DuplicateId(ref id)) if id == "ReadOnly"
+        ));
+        assert_eq!(registry.formats().len(), 1);
+        assert_eq!(registry.get_format("ReadOnly").unwrap().name(), "Read Only");
+
+        let id = FormatId::new("ReadOnly").unwrap();
+        assert_eq!(registry.get_format(&id).unwrap().name(), "Read Only");
+    }
+
+    #[test]
+    fn test_registry_unregister() {
+        let mut registry = ModFormatRegistry::new();
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+        registry
+            .register(Box::new(SpyCompressingFormat::default()))
+            .unwrap();
+        let path = Path::new("test.7z");
+
+        assert!(registry.unregister("Spy"));
+        assert!(!registry.unregister("Spy"));
+        assert!(registry.get_format("Spy").is_none());
+        assert_eq!(registry.formats().len(), 1);
+        assert_eq!(
+            registry.detect_format(path).map(|f| f.id()),
+            Some("ReadOnly")
+        );
+
+        assert!(registry.unregister("ReadOnly"));
+        assert!(registry.detect_format(path).is_none());
+    }
+
+    #[test]
+    fn test_registry_unregister_keeps_lookup() {
+        let mut registry = ModFormatRegistry::new();
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+        registry
+            .register(Box::new(SpyCompressingFormat::default()))
+            .unwrap();
+
+        assert!(registry.unregister(&FormatId::new("ReadOnly").unwrap()));
+        assert_eq!(registry.get_format("Spy").map(|f| f.id()), Some("Spy"));
+        assert!(registry.get_format("ReadOnly").is_none());
+
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+        assert_eq!(
+            registry.get_format("ReadOnly").map(|f| f.id()),
+            Some("ReadOnly")
+        );
+    }
+
+    #[test]
+    fn test_registry_clear() {
+        let mut registry = ModFormatRegistry::new();
+        registry.register(Box::new(ReadOnlyFormat)).unwrap();
+        registry
+            .register(Box::new(SpyCompressingFormat::default()))
+            .unwrap();
+
+        registry.clear();
+        assert!(registry.formats().is_empty());
+        assert!(registry.get_format("ReadOnly").is_none());
+        assert!(registry.detect_format(Path::new("test.7z")).is_none());
+    }
 
     #[test]
     fn test_format_confidence_ordering() {
@@ -175,6 +1189,37 @@ mod tests {
         assert!(FormatConfidence::Convertible > FormatConfidence::Incompatible);
     }
 
+    const ALL_CONFIDENCES: [FormatConfidence; 4] = [
+        FormatConfidence::Incompatible,
+        FormatConfidence::Convertible,
+        FormatConfidence::Compatible,
+        FormatConfidence::Match,
+    ];
+
+    #[test]
+    fn test_format_confidence_serde() {
+        for confidence in ALL_CONFIDENCES {
+            let json = serde_json::to_string(&confidence).unwrap();
+            assert_eq!(json, format!("\"{}\"", confidence));
+            let parsed: FormatConfidence = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, confidence);
+        }
+    }
+
+    #[test]
+    fn test_format_confidence_display_from_str() {
+        for confidence in ALL_CONFIDENCES {
+            let parsed: FormatConfidence = confidence.to_string().parse().unwrap();
+            assert_eq!(parsed, confidence);
+        }
+        assert_eq!(FormatConfidence::Match.to_string(), "Match");
+        assert_eq!(
+            "compatible".parse::<FormatConfidence>().unwrap(),
+            FormatConfidence::Compatible
+        );
+        assert!("Maybe".parse::<FormatConfidence>().is_err());
+    }
+
     #[test]
     fn test_format_confidence_usable() {
         assert!(FormatConfidence::Match.is_usable());
@@ -6,9 +6,11 @@
 //! - [`Mod`] - Trait for accessing mod archive contents
 //! - [`ScriptType`] - Types of installation scripts
 
-use crate::error::ModError;
-use chrono::{DateTime, Utc};
+use crate::error::{ModError, VersionParseError};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
 use std::path::Path;
 
 /// Mod metadata.
@@ -28,7 +30,7 @@ use std::path::Path;
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModInfo {
     /// Nexus Mods mod ID.
     pub id: Option<String>,
@@ -84,6 +86,76 @@ pub struct ModInfo {
     /// Position in mod load order.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub load_order: Option<i32>,
+
+    /// When updates for this mod were last checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update_check: Option<DateTime<Utc>>,
+
+    /// Until when the user asked not to be prompted to endorse this mod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abstained_until: Option<DateTime<Utc>>,
+
+    /// Pending load order position, applied by [`ModInfo::commit_load_order`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_load_order: Option<i32>,
+
+    /// User-assigned free-form tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Whether the mod is enabled; disabled mods stay installed but inactive.
+    ///
+    /// Defaults to `true`, including when missing from serialized data.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Screenshot image bytes.
+    ///
+    /// Use [`ModInfo::strip_screenshot`] to store the image separately
+    /// from the rest of the metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screenshot: Option<Vec<u8>>,
+
+    /// Whether the record changed since it was last saved.
+    ///
+    /// Set by the builder methods; prefer [`is_dirty`](Self::is_dirty) and
+    /// [`mark_clean`](Self::mark_clean) over touching this directly. Not
+    /// serialized.
+    #[serde(skip)]
+    pub dirty: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for ModInfo {
+    fn default() -> Self {
+        Self {
+            id: None,
+            download_id: None,
+            name: String::new(),
+            file_name: String::new(),
+            version: String::new(),
+            machine_version: None,
+            author: None,
+            description: None,
+            category_id: None,
+            custom_category_id: None,
+            website: None,
+            download_date: None,
+            install_date: None,
+            is_endorsed: None,
+            load_order: None,
+            last_update_check: None,
+            abstained_until: None,
+            new_load_order: None,
+            tags: Vec::new(),
+            enabled: default_enabled(),
+            screenshot: None,
+            dirty: false,
+        }
+    }
 }
 
 impl ModInfo {
@@ -96,122 +168,2535 @@ impl ModInfo {
         }
     }
 
+    /// Guess metadata from an archive filename.
+    ///
+    /// Nexus Mods names downloads `Name-ModID-Version[-Timestamp].ext`, with
+    /// spaces in the name replaced by underscores and dots in the version
+    /// replaced by dashes. When the filename follows that convention, this
+    /// sets `name`, `id` and `version`; otherwise the filename stem becomes
+    /// the name. `file_name` is always set to the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let info = ModInfo::from_file_name("SkyUI_5_2SE-12604-5-2SE.7z");
+    /// assert_eq!(info.name, "SkyUI 5 2SE");
+    /// assert_eq!(info.id.as_deref(), Some("12604"));
+    /// assert_eq!(info.version, "5.2SE");
+    /// ```
+    pub fn from_file_name(file_name: &str) -> ModInfo {
+        // Upload timestamps are Unix seconds; anything this long isn't a version part.
+        const MIN_TIMESTAMP_DIGITS: usize = 9;
+
+        let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        let stem = Path::new(file_name)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(file_name);
+
+        let mut info = ModInfo::new(stem, file_name);
+
+        let segments: Vec<&str> = stem.split('-').collect();
+        let Some(id_index) = segments.iter().skip(1).position(|s| is_number(s)) else {
+            return info;
+        };
+        let id_index = id_index + 1;
+
+        let mut version_parts = &segments[id_index + 1..];
+        if let [rest @ .., last] = version_parts {
+            if is_number(last) && last.len() >= MIN_TIMESTAMP_DIGITS {
+                version_parts = rest;
+            }
+        }
+
+        info.name = segments[..id_index].join("-").replace('_', " ");
+        info.id = Some(segments[id_index].to_string());
+        info.version = version_parts.join(".");
+        info
+    }
+
     /// Set the version.
     pub fn with_version(mut self, version: impl Into<String>) -> Self {
         self.version = version.into();
+        self.dirty = true;
         self
     }
 
     /// Set the author.
     pub fn with_author(mut self, author: impl Into<String>) -> Self {
         self.author = Some(author.into());
+        self.dirty = true;
         self
     }
-}
 
-/// Type of installation script in a mod.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ScriptType {
-    /// XML-based configuration script (fomod/ModuleConfig.xml).
-    XmlScript,
+    /// Set the tags, replacing any existing ones.
+    pub fn with_tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self.dirty = true;
+        self
+    }
 
-    /// Legacy ModScript format (for Morrowind/Oblivion).
-    ModScript,
+    /// Add a tag unless an equal one (ignoring case) is already present.
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+            self.dirty = true;
+        }
+    }
 
-    /// WebAssembly-based script.
-    Wasm,
-}
+    /// Check whether the mod has a tag, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.to_lowercase();
+        self.tags.iter().any(|t| t.to_lowercase() == tag)
+    }
 
-/// Trait for accessing mod archive contents.
-///
-/// This trait abstracts over different mod archive formats, allowing
-/// uniform access to files within the mod.
-///
-/// # Example
-///
-/// ```rust,ignore
-/// use nmm_core::Mod;
-///
-/// fn list_textures(mod_archive: &dyn Mod) -> Vec<String> {
-///     mod_archive
-///         .file_list()
-///         .unwrap()
-///         .into_iter()
-///         .filter(|f| f.ends_with(".dds"))
-///         .collect()
-/// }
-/// ```
-pub trait Mod: Send + Sync {
-    /// Get mod metadata.
-    fn info(&self) -> &ModInfo;
+    /// Whether the mod is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
 
-    /// Path to the mod archive file.
-    fn archive_path(&self) -> &Path;
+    /// Enable or disable the mod without uninstalling it.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            self.dirty = true;
+        }
+    }
 
-    /// Format identifier (e.g., "FOMod", "OMod").
-    fn format_id(&self) -> &str;
+    /// Set the install date.
+    pub fn with_install_date(mut self, install_date: DateTime<Utc>) -> Self {
+        self.install_date = Some(install_date);
+        self.dirty = true;
+        self
+    }
 
-    /// List all files in the mod archive.
-    fn file_list(&self) -> Result<Vec<String>, ModError>;
+    /// Check whether the record changed since it was last saved.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
 
-    /// List files in a specific folder within the archive.
+    /// Mark the record as saved.
+    pub fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Parse a version string as strict semver.
     ///
-    /// # Arguments
+    /// Only already-valid semver is accepted, optionally with a leading
+    /// `v`. Use this where a malformed version should be reported rather
+    /// than guessed at.
     ///
-    /// * `folder` - Path to the folder within the archive
-    /// * `recursive` - Whether to include files in subdirectories
-    fn file_list_in_folder(&self, folder: &str, recursive: bool) -> Result<Vec<String>, ModError>;
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let version = ModInfo::parse_version_strict("v2.0.0").unwrap();
+    /// assert_eq!(version, semver::Version::new(2, 0, 0));
+    /// assert!(ModInfo::parse_version_strict("1.2").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionParseError::Empty`] for an empty string and
+    /// [`VersionParseError::Invalid`] if the string isn't valid semver.
+    pub fn parse_version_strict(s: &str) -> Result<semver::Version, VersionParseError> {
+        let trimmed = s.trim();
+        let unprefixed = trimmed
+            .strip_prefix('v')
+            .or_else(|| trimmed.strip_prefix('V'))
+            .unwrap_or(trimmed);
 
-    /// Read a file from the mod archive into memory.
+        if unprefixed.is_empty() {
+            return Err(VersionParseError::Empty);
+        }
+
+        semver::Version::parse(unprefixed).map_err(|source| VersionParseError::Invalid {
+            input: s.to_string(),
+            source,
+        })
+    }
+
+    /// Deserialize a record from a JSON stream.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `path` - Path to the file within the archive
+    /// Returns a [`serde_json::Error`] if reading fails or the JSON is not
+    /// a valid record.
+    pub fn from_reader(reader: impl Read) -> serde_json::Result<ModInfo> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Serialize this record as JSON to a stream.
     ///
     /// # Errors
     ///
-    /// Returns `ModError::FileNotFound` if the file doesn't exist in the archive.
-    fn read_file(&self, path: &str) -> Result<Vec<u8>, ModError>;
+    /// Returns a [`serde_json::Error`] if writing fails.
+    pub fn to_writer(&self, writer: impl Write) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
 
-    /// Get a readable stream for a file in the archive.
+    /// Convert a record exported from the original C# NMM.
     ///
-    /// This is more memory-efficient than `read_file` for large files.
-    fn read_file_stream(&self, path: &str) -> Result<Box<dyn std::io::Read + '_>, ModError>;
+    /// The legacy format uses PascalCase names (`ModName`,
+    /// `HumanReadableVersion`, `InstallDate`, ...). IDs may be strings or
+    /// numbers. Dates without a UTC offset are read as UTC. `MachineVersion`
+    /// is a .NET `System.Version` such as `5.2.0.0`; it's mapped onto semver
+    /// by padding or truncating to three parts. Negative category IDs and
+    /// load order positions are NMM's "none" sentinel and become `None`.
+    /// Values that don't parse (dates, URLs, versions) are dropped rather
+    /// than failing the whole record.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if `value` isn't an object or a
+    /// field has the wrong type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let legacy = serde_json::json!({
+    ///     "ModName": "SkyUI",
+    ///     "FileName": "SkyUI_5_2_SE-12604-5-2SE.7z",
+    ///     "HumanReadableVersion": "5.2SE",
+    /// });
+    ///
+    /// let info = ModInfo::from_legacy_json(legacy).unwrap();
+    /// assert_eq!(info.name, "SkyUI");
+    /// assert_eq!(info.version, "5.2SE");
+    /// ```
+    pub fn from_legacy_json(value: serde_json::Value) -> serde_json::Result<ModInfo> {
+        let legacy: LegacyModInfo = serde_json::from_value(value)?;
 
-    /// Check if the mod has an installation script.
-    fn has_script(&self) -> bool;
+        Ok(ModInfo {
+            id: legacy.id.and_then(legacy_id),
+            download_id: legacy.download_id.and_then(legacy_id),
+            name: legacy.mod_name.unwrap_or_default(),
+            file_name: legacy.file_name.unwrap_or_default(),
+            version: legacy.human_readable_version.unwrap_or_default(),
+            machine_version: legacy.machine_version.as_deref().and_then(legacy_version),
+            author: legacy.author.filter(|a| !a.is_empty()),
+            description: legacy.description.filter(|d| !d.is_empty()),
+            category_id: legacy.category_id.filter(|&id| id >= 0),
+            custom_category_id: legacy.custom_category_id.filter(|&id| id >= 0),
+            website: legacy.website.and_then(|w| url::Url::parse(&w).ok()),
+            download_date: legacy.download_date.as_deref().and_then(legacy_date),
+            install_date: legacy.install_date.as_deref().and_then(legacy_date),
+            is_endorsed: legacy.is_endorsed,
+            load_order: legacy.place_in_mod_load_order.filter(|&i| i >= 0),
+            new_load_order: legacy.new_place_in_mod_load_order.filter(|&i| i >= 0),
+            ..ModInfo::default()
+        })
+    }
 
-    /// Get the script content and type if present.
-    fn script_content(&self) -> Option<(ScriptType, String)>;
+    /// Downscale the screenshot to fit within `max_dim` pixels.
+    ///
+    /// PNG, JPEG, GIF and BMP screenshots are supported. The aspect ratio is
+    /// preserved and the result is PNG-encoded.
+    /// Images already within the bound are re-encoded at their original
+    /// size. Returns `Ok(None)` when no screenshot is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`image::ImageError`] if the screenshot can't be decoded or
+    /// the thumbnail can't be encoded.
+    #[cfg(feature = "image")]
+    pub fn screenshot_thumbnail(&self, max_dim: u32) -> Result<Option<Vec<u8>>, image::ImageError> {
+        let Some(bytes) = &self.screenshot else {
+            return Ok(None);
+        };
 
-    /// Path to the screenshot within the archive (if any).
-    fn screenshot_path(&self) -> Option<&str>;
-}
+        let mut img = image::load_from_memory(bytes)?;
+        if img.width() > max_dim || img.height() > max_dim {
+            img = img.thumbnail(max_dim, max_dim);
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut out = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut out, image::ImageFormat::Png)?;
+        Ok(Some(out.into_inner()))
+    }
 
-    #[test]
-    fn test_mod_info_builder() {
-        let info = ModInfo::new("Test Mod", "TestMod.7z")
-            .with_version("1.0.0")
-            .with_author("Test Author");
+    /// Split off the screenshot, leaving the lightweight metadata.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let info = ModInfo {
+    ///     screenshot: Some(vec![0x89, b'P', b'N', b'G']),
+    ///     ..ModInfo::new("My Cool Mod", "MyCoolMod.7z")
+    /// };
+    ///
+    /// let (lite, screenshot) = info.strip_screenshot();
+    /// assert_eq!(lite.name, "My Cool Mod");
+    /// assert_eq!(screenshot.map(|s| s.len()), Some(4));
+    /// ```
+    pub fn strip_screenshot(self) -> (ModInfoLite, Option<Vec<u8>>) {
+        let lite = ModInfoLite {
+            id: self.id,
+            download_id: self.download_id,
+            name: self.name,
+            file_name: self.file_name,
+            version: self.version,
+            machine_version: self.machine_version,
+            author: self.author,
+            description: self.description,
+            category_id: self.category_id,
+            custom_category_id: self.custom_category_id,
+            website: self.website,
+            download_date: self.download_date,
+            install_date: self.install_date,
+            is_endorsed: self.is_endorsed,
+            load_order: self.load_order,
+            last_update_check: self.last_update_check,
+            abstained_until: self.abstained_until,
+            new_load_order: self.new_load_order,
+            tags: self.tags,
+            enabled: self.enabled,
+        };
+        (lite, self.screenshot)
+    }
 
-        assert_eq!(info.name, "Test Mod");
-        assert_eq!(info.file_name, "TestMod.7z");
-        assert_eq!(info.version, "1.0.0");
-        assert_eq!(info.author, Some("Test Author".into()));
+    /// Canonicalize string fields before persistence.
+    ///
+    /// Trims whitespace from `name`, `version` and `author`, lowercases
+    /// the `file_name` extension, and turns a blank `author` or
+    /// `description` into `None`. This keeps records that differ only by
+    /// stray whitespace or extension case from looking like different mods.
+    /// Marks the record dirty if anything changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let mut info = ModInfo::new("  My Mod ", "MyMod.ZIP");
+    /// info.normalize();
+    ///
+    /// assert_eq!(info.name, "My Mod");
+    /// assert_eq!(info.file_name, "MyMod.zip");
+    /// ```
+    pub fn normalize(&mut self) {
+        fn trim_in_place(s: &mut String) -> bool {
+            let trimmed = s.trim();
+            if trimmed.len() == s.len() {
+                return false;
+            }
+            *s = trimmed.to_string();
+            true
+        }
+
+        let mut changed = trim_in_place(&mut self.name);
+        changed |= trim_in_place(&mut self.version);
+
+        if let Some(author) = &mut self.author {
+            changed |= trim_in_place(author);
+        }
+        if self.author.as_deref().is_some_and(str::is_empty) {
+            self.author = None;
+            changed = true;
+        }
+
+        if self
+            .description
+            .as_deref()
+            .is_some_and(|d| d.trim().is_empty())
+        {
+            self.description = None;
+            changed = true;
+        }
+
+        // Only a short alphanumeric suffix is an extension; a trailing
+        // version like "5.2SE" must keep its case. Extensions start with a
+        // letter, except for 7z.
+        let is_extension = |ext: &str| {
+            (1..=5).contains(&ext.len())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                && (ext.starts_with(|c: char| c.is_ascii_alphabetic())
+                    || ext.eq_ignore_ascii_case("7z"))
+        };
+        if let Some(ext) = std::path::Path::new(&self.file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .filter(|e| is_extension(e) && e.chars().any(|c| c.is_ascii_uppercase()))
+        {
+            let stem_len = self.file_name.len() - ext.len();
+            self.file_name = format!(
+                "{}{}",
+                &self.file_name[..stem_len],
+                ext.to_ascii_lowercase()
+            );
+            changed = true;
+        }
+
+        if changed {
+            self.dirty = true;
+        }
     }
 
-    #[test]
-    fn test_mod_info_serialization() {
-        let info = ModInfo::new("Test Mod", "TestMod.7z").with_version("1.0.0");
+    /// Merge fields from `other` into this record.
+    ///
+    /// With `overwrite`, every field `other` has a value for replaces the
+    /// one here; otherwise only empty fields here are filled in. Empty
+    /// strings and `None` count as missing. [`enabled`](Self::enabled) is
+    /// local state and is never merged. Marks the record dirty if anything
+    /// changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let mut info = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.1");
+    /// let remote = ModInfo::new("SkyUI", "").with_version("5.2").with_author("schlangster");
+    ///
+    /// info.update_from(&remote, false);
+    /// assert_eq!(info.version, "5.1");
+    /// assert_eq!(info.author.as_deref(), Some("schlangster"));
+    /// ```
+    pub fn update_from(&mut self, other: &ModInfo, overwrite: bool) {
+        fn merge_str(dst: &mut String, src: &str, overwrite: bool) -> bool {
+            if src.is_empty() || dst == src || !(overwrite || dst.is_empty()) {
+                return false;
+            }
+            *dst = src.to_string();
+            true
+        }
 
-        let json = serde_json::to_string(&info).unwrap();
-        let parsed: ModInfo = serde_json::from_str(&json).unwrap();
+        fn merge_opt<T: Clone + PartialEq>(
+            dst: &mut Option<T>,
+            src: &Option<T>,
+            overwrite: bool,
+        ) -> bool {
+            if src.is_none() || dst == src || !(overwrite || dst.is_none()) {
+                return false;
+            }
+            dst.clone_from(src);
+            true
+        }
 
-        assert_eq!(parsed.name, info.name);
-        assert_eq!(parsed.version, info.version);
+        fn merge_opt_str(dst: &mut Option<String>, src: &Option<String>, overwrite: bool) -> bool {
+            let Some(src) = src.as_deref().filter(|s| !s.is_empty()) else {
+                return false;
+            };
+            let dst_missing = dst.as_deref().map_or(true, str::is_empty);
+            if dst.as_deref() == Some(src) || !(overwrite || dst_missing) {
+                return false;
+            }
+            *dst = Some(src.to_string());
+            true
+        }
+
+        fn merge_vec<T: Clone + PartialEq>(dst: &mut Vec<T>, src: &[T], overwrite: bool) -> bool {
+            if src.is_empty() || dst == src || !(overwrite || dst.is_empty()) {
+                return false;
+            }
+            *dst = src.to_vec();
+            true
+        }
+
+        let changed = [
+            merge_opt_str(&mut self.id, &other.id, overwrite),
+            merge_opt_str(&mut self.download_id, &other.download_id, overwrite),
+            merge_str(&mut self.name, &other.name, overwrite),
+            merge_str(&mut self.file_name, &other.file_name, overwrite),
+            merge_str(&mut self.version, &other.version, overwrite),
+            merge_opt(&mut self.machine_version, &other.machine_version, overwrite),
+            merge_opt_str(&mut self.author, &other.author, overwrite),
+            merge_opt_str(&mut self.description, &other.description, overwrite),
+            merge_opt(&mut self.category_id, &other.category_id, overwrite),
+            merge_opt(
+                &mut self.custom_category_id,
+                &other.custom_category_id,
+                overwrite,
+            ),
+            merge_opt(&mut self.website, &other.website, overwrite),
+            merge_opt(&mut self.download_date, &other.download_date, overwrite),
+            merge_opt(&mut self.install_date, &other.install_date, overwrite),
+            merge_opt(&mut self.is_endorsed, &other.is_endorsed, overwrite),
+            merge_opt(&mut self.load_order, &other.load_order, overwrite),
+            merge_opt(&mut self.new_load_order, &other.new_load_order, overwrite),
+            merge_opt(
+                &mut self.last_update_check,
+                &other.last_update_check,
+                overwrite,
+            ),
+            merge_opt(&mut self.abstained_until, &other.abstained_until, overwrite),
+            merge_opt(&mut self.screenshot, &other.screenshot, overwrite),
+            merge_vec(&mut self.tags, &other.tags, overwrite),
+        ];
+
+        if changed.contains(&true) {
+            self.dirty = true;
+        }
+    }
+
+    /// Fold several partial records into one.
+    ///
+    /// Starts from the first source and fills its empty fields from each
+    /// later source in turn, so earlier sources win. Returns a default
+    /// record if `sources` is empty.
+    pub fn coalesce(sources: &[ModInfo]) -> ModInfo {
+        let Some((first, rest)) = sources.split_first() else {
+            return ModInfo::default();
+        };
+
+        let mut merged = first.clone();
+        for source in rest {
+            merged.update_from(source, false);
+        }
+        merged
+    }
+
+    /// Build a lowercase search document from the searchable fields.
+    ///
+    /// Joins the name, author, description and website host with single
+    /// spaces, collapsing any internal whitespace.
+    pub fn as_query_document(&self) -> String {
+        let host = self.website.as_ref().and_then(|url| url.host_str());
+
+        [
+            Some(self.name.as_str()),
+            self.author.as_deref(),
+            self.description.as_deref(),
+            host,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(str::split_whitespace)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+
+    /// Check whether every search term appears in the query document.
+    ///
+    /// Matching is case-insensitive substring matching. Blank terms are
+    /// ignored, so an empty query matches every mod.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let info = ModInfo::new("SkyUI", "SkyUI.7z").with_author("schlangster");
+    /// assert!(info.matches_query(&["sky", "SCHLANG"]));
+    /// assert!(!info.matches_query(&["sky", "fallout"]));
+    /// ```
+    pub fn matches_query(&self, terms: &[&str]) -> bool {
+        let document = self.as_query_document();
+        terms
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .all(|t| document.contains(&t.to_lowercase()))
+    }
+
+    /// Compute when this mod's next update check is due.
+    ///
+    /// The due time is `interval` after [`last_update_check`](Self::last_update_check),
+    /// shifted by up to ±10% of `interval`. A mod that has never been checked
+    /// is due within the first 10% of `interval` from `now`. The shift is
+    /// derived from a hash of the mod's ID and file name plus `jitter_seed`,
+    /// so it's stable for a given mod but spreads mods apart, avoiding a burst
+    /// of API calls at startup.
+    pub fn next_check_due(
+        &self,
+        now: DateTime<Utc>,
+        interval: Duration,
+        jitter_seed: u64,
+    ) -> DateTime<Utc> {
+        // Huge intervals saturate at the representable range instead of
+        // overflowing.
+        fn saturating_add(time: DateTime<Utc>, delta: Duration) -> DateTime<Utc> {
+            time.checked_add_signed(delta)
+                .unwrap_or(if delta < Duration::zero() {
+                    DateTime::<Utc>::MIN_UTC
+                } else {
+                    DateTime::<Utc>::MAX_UTC
+                })
+        }
+
+        // `jitter` is per ten-thousand of the interval.
+        let jitter_offset = |jitter: i64| {
+            let interval_ms = interval.num_milliseconds();
+            let ms = interval_ms
+                .checked_mul(jitter)
+                .map_or(interval_ms / 10_000 * jitter, |ms| ms / 10_000);
+            Duration::milliseconds(ms)
+        };
+
+        let hash = self.jitter_hash(jitter_seed);
+        match self.last_update_check {
+            Some(last) => {
+                let jitter = (hash % 2001) as i64 - 1000;
+                let offset = jitter_offset(jitter);
+                let delay = interval
+                    .checked_add(&offset)
+                    .unwrap_or(if offset < Duration::zero() {
+                        Duration::MIN
+                    } else {
+                        Duration::MAX
+                    });
+                saturating_add(last, delay)
+            }
+            None => {
+                let jitter = (hash % 1001) as i64;
+                saturating_add(now, jitter_offset(jitter))
+            }
+        }
+    }
+
+    /// Apply a pending [`new_load_order`](Self::new_load_order).
+    ///
+    /// Moves it into [`load_order`](Self::load_order) and clears it. Does
+    /// nothing if no reorder is pending.
+    pub fn commit_load_order(&mut self) {
+        if let Some(order) = self.new_load_order.take() {
+            self.load_order = Some(order);
+            self.dirty = true;
+        }
+    }
+
+    /// Suppress endorsement prompts for this mod until `until`.
+    pub fn abstain_until(&mut self, until: DateTime<Utc>) {
+        self.abstained_until = Some(until);
+        self.dirty = true;
+    }
+
+    /// Whether the user should be prompted to endorse this mod at `now`.
+    ///
+    /// True only when the endorsement state is unknown and `now` is not
+    /// inside an abstain window set by [`abstain_until`](Self::abstain_until).
+    pub fn should_prompt_endorsement(&self, now: DateTime<Utc>) -> bool {
+        self.is_endorsed.is_none() && self.abstained_until.map_or(true, |until| now >= until)
+    }
+
+    /// Stable FNV-1a hash of the mod's identity, seeded by `seed`.
+    fn jitter_hash(&self, seed: u64) -> u64 {
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let id = self.id.as_deref().unwrap_or_default();
+        let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+        for byte in id.bytes().chain([0xff]).chain(self.file_name.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        hash
+    }
+
+    /// Check whether the website is hosted on Nexus Mods.
+    ///
+    /// Matches `nexusmods.com` and any of its subdomains. This is a
+    /// structural check only; no network request is made.
+    pub fn website_is_nexus(&self) -> bool {
+        const NEXUS_DOMAIN: &str = "nexusmods.com";
+
+        let Some(host) = self.website.as_ref().and_then(|url| url.host_str()) else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        host == NEXUS_DOMAIN
+            || host
+                .strip_suffix(NEXUS_DOMAIN)
+                .is_some_and(|sub| sub.ends_with('.'))
+    }
+
+    /// Check whether the website uses HTTPS.
+    ///
+    /// Returns `false` if no website is set.
+    pub fn website_scheme_ok(&self) -> bool {
+        self.website
+            .as_ref()
+            .is_some_and(|url| url.scheme() == "https")
+    }
+
+    /// Build a normalized key for fuzzy matching across sources.
+    ///
+    /// The key is a slug of the name (lowercase alphanumeric words joined
+    /// by `-`) plus `@major.minor` when a version can be read from
+    /// `machine_version` or the leading digits of `version`. Hosts use it
+    /// to propose matches between, say, an installed mod and a Nexus
+    /// search result whose names and versions are formatted differently.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let local = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.2 SE");
+    /// let remote = ModInfo::new("skyui", "skyui_5_2.zip").with_version("v5.2");
+    /// assert_eq!(local.semantic_key(), remote.semantic_key());
+    /// assert_eq!(local.semantic_key(), "skyui@5.2");
+    /// ```
+    pub fn semantic_key(&self) -> String {
+        let slug = self
+            .name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+
+        let major_minor = match &self.machine_version {
+            Some(v) => Some((v.major, v.minor)),
+            None => leading_major_minor(&self.version),
+        };
+
+        match major_minor {
+            Some((major, minor)) => format!("{}@{}.{}", slug, major, minor),
+            None => slug,
+        }
+    }
+
+    /// Roughly estimate the memory used by this record, in bytes.
+    ///
+    /// Sums the lengths of the string fields, the website URL and the
+    /// screenshot on top of the struct's own size. It isn't exact, but it
+    /// scales with the real footprint, which is enough for cache budgeting.
+    pub fn estimated_size_bytes(&self) -> usize {
+        let optional = |s: &Option<String>| s.as_ref().map_or(0, String::len);
+
+        std::mem::size_of::<Self>()
+            + self.name.len()
+            + self.file_name.len()
+            + self.version.len()
+            + optional(&self.id)
+            + optional(&self.download_id)
+            + optional(&self.author)
+            + optional(&self.description)
+            + self.website.as_ref().map_or(0, |url| url.as_str().len())
+            + self.screenshot.as_ref().map_or(0, Vec::len)
+            + self
+                .tags
+                .iter()
+                .map(|t| std::mem::size_of::<String>() + t.len())
+                .sum::<usize>()
+    }
+
+    /// Compare two records field by field.
+    ///
+    /// Returns one [`ModInfoFieldChange`] for each field whose value differs
+    /// between `self` (old) and `other` (new), in declaration order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::ModInfo;
+    ///
+    /// let old = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.1");
+    /// let new = old.clone().with_version("5.2");
+    ///
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].field, "version");
+    /// ```
+    pub fn diff(&self, other: &ModInfo) -> Vec<ModInfoFieldChange> {
+        fn display<T: ToString>(value: &Option<T>) -> Option<String> {
+            value.as_ref().map(ToString::to_string)
+        }
+
+        let mut changes = Vec::new();
+        let mut check = |field: &'static str, old: Option<String>, new: Option<String>| {
+            if old != new {
+                changes.push(ModInfoFieldChange { field, old, new });
+            }
+        };
+
+        check("id", self.id.clone(), other.id.clone());
+        check(
+            "download_id",
+            self.download_id.clone(),
+            other.download_id.clone(),
+        );
+        check("name", Some(self.name.clone()), Some(other.name.clone()));
+        check(
+            "file_name",
+            Some(self.file_name.clone()),
+            Some(other.file_name.clone()),
+        );
+        check(
+            "version",
+            Some(self.version.clone()),
+            Some(other.version.clone()),
+        );
+        check(
+            "machine_version",
+            display(&self.machine_version),
+            display(&other.machine_version),
+        );
+        check("author", self.author.clone(), other.author.clone());
+        check(
+            "description",
+            self.description.clone(),
+            other.description.clone(),
+        );
+        check(
+            "category_id",
+            display(&self.category_id),
+            display(&other.category_id),
+        );
+        check(
+            "custom_category_id",
+            display(&self.custom_category_id),
+            display(&other.custom_category_id),
+        );
+        check("website", display(&self.website), display(&other.website));
+        check(
+            "download_date",
+            display(&self.download_date),
+            display(&other.download_date),
+        );
+        check(
+            "install_date",
+            display(&self.install_date),
+            display(&other.install_date),
+        );
+        check(
+            "is_endorsed",
+            display(&self.is_endorsed),
+            display(&other.is_endorsed),
+        );
+        check(
+            "load_order",
+            display(&self.load_order),
+            display(&other.load_order),
+        );
+
+        check(
+            "last_update_check",
+            display(&self.last_update_check),
+            display(&other.last_update_check),
+        );
+
+        check(
+            "abstained_until",
+            display(&self.abstained_until),
+            display(&other.abstained_until),
+        );
+
+        check(
+            "new_load_order",
+            display(&self.new_load_order),
+            display(&other.new_load_order),
+        );
+
+        let tags = |tags: &[String]| (!tags.is_empty()).then(|| tags.join(", "));
+        check("tags", tags(&self.tags), tags(&other.tags));
+
+        check(
+            "enabled",
+            Some(self.enabled.to_string()),
+            Some(other.enabled.to_string()),
+        );
+
+        // Compare the bytes, but only show the size.
+        if self.screenshot != other.screenshot {
+            let size = |s: &Option<Vec<u8>>| s.as_ref().map(|b| format!("{} bytes", b.len()));
+            changes.push(ModInfoFieldChange {
+                field: "screenshot",
+                old: size(&self.screenshot),
+                new: size(&other.screenshot),
+            });
+        }
+
+        changes
+    }
+
+    /// Format the mod for display using the given options.
+    ///
+    /// With default options this matches the [`Display`](fmt::Display)
+    /// output (`Name vVersion by Author`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::{ModInfo, ModInfoDisplay};
+    ///
+    /// let info = ModInfo::new("SkyUI", "SkyUI.7z")
+    ///     .with_version("5.2")
+    ///     .with_author("schlangster");
+    ///
+    /// let opts = ModInfoDisplay::new().show_author(false);
+    /// assert_eq!(info.display_with(&opts), "SkyUI v5.2");
+    /// ```
+    pub fn display_with(&self, options: &ModInfoDisplay<'_>) -> String {
+        let mut out = String::new();
+        self.write_display(&mut out, options, "v", "by")
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Format as `Name vVersion by Author` with translated labels.
+    ///
+    /// With [`ModInfoLabels::default`] this matches the
+    /// [`Display`](fmt::Display) output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nmm_core::{ModInfo, ModInfoLabels};
+    ///
+    /// let info = ModInfo::new("SkyUI", "SkyUI.7z")
+    ///     .with_version("5.2")
+    ///     .with_author("schlangster");
+    ///
+    /// let labels = ModInfoLabels {
+    ///     author_connector: "von".to_string(),
+    ///     ..ModInfoLabels::default()
+    /// };
+    /// assert_eq!(info.display_localized(&labels), "SkyUI v5.2 von schlangster");
+    /// ```
+    pub fn display_localized(&self, labels: &ModInfoLabels) -> String {
+        let mut out = String::new();
+        self.write_display(
+            &mut out,
+            &ModInfoDisplay::new(),
+            &labels.version_prefix,
+            &labels.author_connector,
+        )
+        .expect("writing to a String cannot fail");
+        out
+    }
+
+    fn write_display(
+        &self,
+        w: &mut impl fmt::Write,
+        options: &ModInfoDisplay<'_>,
+        version_prefix: &str,
+        author_connector: &str,
+    ) -> fmt::Result {
+        w.write_str(&self.name)?;
+
+        if options.show_version && !self.version.is_empty() {
+            write!(w, " {}{}", version_prefix, self.version)?;
+        }
+
+        if options.show_author {
+            if let Some(author) = &self.author {
+                write!(w, " {} {}", author_connector, author)?;
+            }
+        }
+
+        if let Some(resolver) = options.category_resolver {
+            let category = self
+                .custom_category_id
+                .or(self.category_id)
+                .and_then(|id| resolver.category_name(id));
+            if let Some(category) = category {
+                write!(w, " [{}]", category)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_display(f, &ModInfoDisplay::new(), "v", "by")
+    }
+}
+
+/// Labels used by [`ModInfo::display_localized`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModInfoLabels {
+    /// Written directly before the version (default `v`).
+    pub version_prefix: String,
+
+    /// Written between the version and the author (default `by`).
+    pub author_connector: String,
+}
+
+impl Default for ModInfoLabels {
+    fn default() -> Self {
+        Self {
+            version_prefix: "v".to_string(),
+            author_connector: "by".to_string(),
+        }
+    }
+}
+
+/// Check that the mods' committed load orders are `0..n` with no gaps.
+///
+/// Every mod must have a [`load_order`](ModInfo::load_order), and together
+/// they must be a permutation of `0..mods.len()`.
+///
+/// # Errors
+///
+/// Returns a description of the first missing, out-of-range or duplicate
+/// position found.
+///
+/// # Example
+///
+/// ```rust
+/// use nmm_core::{validate_load_order, ModInfo};
+///
+/// let mut a = ModInfo::new("A", "A.7z");
+/// let mut b = ModInfo::new("B", "B.7z");
+/// a.load_order = Some(1);
+/// b.load_order = Some(0);
+/// assert!(validate_load_order(&[a, b]).is_ok());
+/// ```
+pub fn validate_load_order(mods: &[ModInfo]) -> Result<(), String> {
+    let mut slots: Vec<Option<&str>> = vec![None; mods.len()];
+
+    for info in mods {
+        let order = info
+            .load_order
+            .ok_or_else(|| format!("{} has no load order", info.name))?;
+        let slot = usize::try_from(order)
+            .ok()
+            .and_then(|i| slots.get_mut(i))
+            .ok_or_else(|| {
+                format!(
+                    "{} has load order {}, outside 0..{}",
+                    info.name,
+                    order,
+                    mods.len()
+                )
+            })?;
+        if let Some(other) = slot.replace(&info.name) {
+            return Err(format!(
+                "{} and {} share load order {}",
+                other, info.name, order
+            ));
+        }
+    }
+
+    // n mods filling distinct slots in 0..n leaves no gaps.
+    Ok(())
+}
+
+/// Deserialize a JSON array of records from a stream.
+///
+/// # Errors
+///
+/// Returns a [`serde_json::Error`] if reading fails or the JSON is not an
+/// array of valid records.
+///
+/// # Example
+///
+/// ```rust
+/// use nmm_core::{read_mods_json, write_mods_json, ModInfo};
+///
+/// let mut buf = Vec::new();
+/// write_mods_json(&mut buf, &[ModInfo::new("SkyUI", "SkyUI.7z")]).unwrap();
+///
+/// let mods = read_mods_json(buf.as_slice()).unwrap();
+/// assert_eq!(mods[0].name, "SkyUI");
+/// ```
+pub fn read_mods_json(reader: impl Read) -> serde_json::Result<Vec<ModInfo>> {
+    serde_json::from_reader(reader)
+}
+
+/// Serialize records as a JSON array to a stream.
+///
+/// # Errors
+///
+/// Returns a [`serde_json::Error`] if writing fails.
+pub fn write_mods_json(writer: impl Write, mods: &[ModInfo]) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, mods)
+}
+
+/// Mod record as exported by the original C# NMM.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LegacyModInfo {
+    id: Option<serde_json::Value>,
+    download_id: Option<serde_json::Value>,
+    mod_name: Option<String>,
+    file_name: Option<String>,
+    human_readable_version: Option<String>,
+    machine_version: Option<String>,
+    author: Option<String>,
+    description: Option<String>,
+    category_id: Option<i32>,
+    custom_category_id: Option<i32>,
+    website: Option<String>,
+    download_date: Option<String>,
+    install_date: Option<String>,
+    is_endorsed: Option<bool>,
+    place_in_mod_load_order: Option<i32>,
+    new_place_in_mod_load_order: Option<i32>,
+}
+
+/// Read a legacy ID, which may be a string or a number.
+fn legacy_id(value: serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Read a legacy version: semver, or a .NET `System.Version` of one to four
+/// numeric parts, padded or truncated to `major.minor.patch`.
+fn legacy_version(value: &str) -> Option<semver::Version> {
+    let value = value.trim();
+    if let Ok(version) = semver::Version::parse(value) {
+        return Some(version);
+    }
+
+    let parts = value
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if !(1..=4).contains(&parts.len()) {
+        return None;
+    }
+    let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+    Some(semver::Version::new(part(0), part(1), part(2)))
+}
+
+/// Read a legacy date, assuming UTC when no offset is given.
+fn legacy_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|d| d.with_timezone(&Utc))
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .map(|d| d.and_utc())
+        })
+        .ok()
+}
+
+/// Read `major.minor` from the start of a loose version string.
+///
+/// Accepts an optional leading `v`; a missing minor is treated as 0.
+fn leading_major_minor(version: &str) -> Option<(u64, u64)> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+
+    let mut parts = version.split('.').map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u64>().ok()
+    });
+
+    let major = parts.next().flatten()?;
+    let minor = parts.next().flatten().unwrap_or(0);
+    Some((major, minor))
+}
+
+/// A single field that differs between two [`ModInfo`] records.
+///
+/// Produced by [`ModInfo::diff`]. Values are display strings; `None`
+/// means the field was unset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModInfoFieldChange {
+    /// Name of the changed field (e.g., `"version"`).
+    pub field: &'static str,
+
+    /// Value before the change.
+    pub old: Option<String>,
+
+    /// Value after the change.
+    pub new: Option<String>,
+}
+
+/// Mod metadata without the screenshot.
+///
+/// Screenshots can be large, so hosts that keep many records in memory
+/// or serialize them often can store this instead of [`ModInfo`] and load
+/// the screenshot on demand. See [`ModInfo::strip_screenshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModInfoLite {
+    /// Nexus Mods mod ID.
+    pub id: Option<String>,
+
+    /// Nexus Mods download ID.
+    pub download_id: Option<String>,
+
+    /// Display name of the mod.
+    pub name: String,
+
+    /// Archive filename.
+    pub file_name: String,
+
+    /// Human-readable version string.
+    pub version: String,
+
+    /// Parsed semantic version (if applicable).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub machine_version: Option<semver::Version>,
+
+    /// Mod author.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Mod description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Nexus Mods category ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i32>,
+
+    /// User-assigned custom category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_category_id: Option<i32>,
+
+    /// Mod website URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<url::Url>,
+
+    /// When the mod was downloaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_date: Option<DateTime<Utc>>,
+
+    /// When the mod was installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_date: Option<DateTime<Utc>>,
+
+    /// Whether the user has endorsed this mod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_endorsed: Option<bool>,
+
+    /// Position in mod load order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_order: Option<i32>,
+
+    /// When updates for this mod were last checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update_check: Option<DateTime<Utc>>,
+
+    /// Until when the user asked not to be prompted to endorse this mod.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abstained_until: Option<DateTime<Utc>>,
+
+    /// Pending load order position, applied by [`ModInfo::commit_load_order`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_load_order: Option<i32>,
+
+    /// User-assigned free-form tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Whether the mod is enabled; disabled mods stay installed but inactive.
+    ///
+    /// Defaults to `true`, including when missing from serialized data.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Default for ModInfoLite {
+    fn default() -> Self {
+        ModInfo::default().strip_screenshot().0
+    }
+}
+
+impl ModInfoLite {
+    /// Reattach a screenshot, producing a full [`ModInfo`].
+    pub fn with_screenshot(self, screenshot: Option<Vec<u8>>) -> ModInfo {
+        ModInfo {
+            id: self.id,
+            download_id: self.download_id,
+            name: self.name,
+            file_name: self.file_name,
+            version: self.version,
+            machine_version: self.machine_version,
+            author: self.author,
+            description: self.description,
+            category_id: self.category_id,
+            custom_category_id: self.custom_category_id,
+            website: self.website,
+            download_date: self.download_date,
+            install_date: self.install_date,
+            is_endorsed: self.is_endorsed,
+            load_order: self.load_order,
+            last_update_check: self.last_update_check,
+            abstained_until: self.abstained_until,
+            new_load_order: self.new_load_order,
+            tags: self.tags,
+            enabled: self.enabled,
+            screenshot,
+            dirty: false,
+        }
+    }
+}
+
+/// Resolves category IDs to display names.
+///
+/// Category names come from Nexus Mods or user configuration, so
+/// `nmm-core` only defines the lookup.
+pub trait CategoryResolver {
+    /// Get the display name for a category ID.
+    fn category_name(&self, category_id: i32) -> Option<&str>;
+}
+
+/// Options for formatting a [`ModInfo`] with [`ModInfo::display_with`].
+///
+/// The defaults show the version and author but no category, matching
+/// the [`Display`](fmt::Display) impl.
+#[derive(Clone, Copy)]
+pub struct ModInfoDisplay<'a> {
+    show_version: bool,
+    show_author: bool,
+    category_resolver: Option<&'a dyn CategoryResolver>,
+}
+
+impl Default for ModInfoDisplay<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ModInfoDisplay<'a> {
+    /// Create display options matching the default `Display` output.
+    pub fn new() -> Self {
+        Self {
+            show_version: true,
+            show_author: true,
+            category_resolver: None,
+        }
+    }
+
+    /// Set whether the version is shown.
+    pub fn show_version(mut self, show: bool) -> Self {
+        self.show_version = show;
+        self
+    }
+
+    /// Set whether the author is shown.
+    pub fn show_author(mut self, show: bool) -> Self {
+        self.show_author = show;
+        self
+    }
+
+    /// Show the category, resolved to a name by `resolver`.
+    ///
+    /// The custom category takes precedence over the Nexus category.
+    /// Pass `None` to hide the category.
+    pub fn show_category(mut self, resolver: Option<&'a dyn CategoryResolver>) -> Self {
+        self.category_resolver = resolver;
+        self
+    }
+}
+
+impl fmt::Debug for ModInfoDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModInfoDisplay")
+            .field("show_version", &self.show_version)
+            .field("show_author", &self.show_author)
+            .field("show_category", &self.category_resolver.is_some())
+            .finish()
+    }
+}
+
+/// Type of installation script in a mod.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptType {
+    /// XML-based configuration script (fomod/ModuleConfig.xml).
+    XmlScript,
+
+    /// Legacy ModScript format (for Morrowind/Oblivion).
+    ModScript,
+
+    /// WebAssembly-based script.
+    Wasm,
+}
+
+/// Trait for accessing mod archive contents.
+///
+/// This trait abstracts over different mod archive formats, allowing
+/// uniform access to files within the mod.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use nmm_core::Mod;
+///
+/// fn list_textures(mod_archive: &dyn Mod) -> Vec<String> {
+///     mod_archive
+///         .file_list()
+///         .unwrap()
+///         .into_iter()
+///         .filter(|f| f.ends_with(".dds"))
+///         .collect()
+/// }
+/// ```
+pub trait Mod: Send + Sync {
+    /// Get mod metadata.
+    fn info(&self) -> &ModInfo;
+
+    /// Path to the mod archive file.
+    fn archive_path(&self) -> &Path;
+
+    /// Format identifier (e.g., "FOMod", "OMod").
+    fn format_id(&self) -> &str;
+
+    /// List all files in the mod archive.
+    fn file_list(&self) -> Result<Vec<String>, ModError>;
+
+    /// List files in a specific folder within the archive.
+    ///
+    /// # Arguments
+    ///
+    /// * `folder` - Path to the folder within the archive
+    /// * `recursive` - Whether to include files in subdirectories
+    fn file_list_in_folder(&self, folder: &str, recursive: bool) -> Result<Vec<String>, ModError>;
+
+    /// Read a file from the mod archive into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file within the archive
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModError::FileNotFound` if the file doesn't exist in the archive.
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, ModError>;
+
+    /// Get a readable stream for a file in the archive.
+    ///
+    /// This is more memory-efficient than `read_file` for large files.
+    fn read_file_stream(&self, path: &str) -> Result<Box<dyn std::io::Read + '_>, ModError>;
+
+    /// Check if the mod has an installation script.
+    fn has_script(&self) -> bool;
+
+    /// Get the script content and type if present.
+    fn script_content(&self) -> Option<(ScriptType, String)>;
+
+    /// Path to the screenshot within the archive (if any).
+    fn screenshot_path(&self) -> Option<&str>;
+
+    /// Read the screenshot from the archive.
+    ///
+    /// Returns `Ok(None)` when the mod has no screenshot. The result can be
+    /// stored in [`ModInfo::screenshot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`read_file`](Self::read_file).
+    fn screenshot_bytes(&self) -> Result<Option<Vec<u8>>, ModError> {
+        self.screenshot_path()
+            .map(|path| self.read_file(path))
+            .transpose()
+    }
+
+    /// Check that every file in the archive can be read.
+    ///
+    /// The default implementation fully reads each file through
+    /// [`read_file_stream`](Self::read_file_stream). Archive-backed
+    /// implementations can override this to check CRCs more cheaply.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModError::ArchiveError` for the first file that fails to read.
+    fn verify_contents(&self) -> Result<(), ModError> {
+        for path in self.file_list()? {
+            let result = self
+                .read_file_stream(&path)
+                .and_then(|mut stream| Ok(std::io::copy(&mut stream, &mut std::io::sink())?));
+            if let Err(e) = result {
+                return Err(ModError::ArchiveError(format!("{}: {}", path, e)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::io::Cursor;
+
+    /// In-memory mod; files mapped to `None` fail partway through reading.
+    struct MockMod {
+        info: ModInfo,
+        files: BTreeMap<String, Option<Vec<u8>>>,
+        screenshot_path: Option<String>,
+    }
+
+    impl MockMod {
+        fn new(files: &[(&str, Option<&[u8]>)]) -> Self {
+            Self {
+                info: ModInfo::new("Mock Mod", "MockMod.7z"),
+                files: files
+                    .iter()
+                    .map(|(path, data)| (path.to_string(), data.map(<[u8]>::to_vec)))
+                    .collect(),
+                screenshot_path: None,
+            }
+        }
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "CRC mismatch",
+            ))
+        }
+    }
+
+    impl Mod for MockMod {
+        fn info(&self) -> &ModInfo {
+            &self.info
+        }
+        fn archive_path(&self) -> &Path {
+            Path::new("MockMod.7z")
+        }
+        fn format_id(&self) -> &str {
+            "Mock"
+        }
+        fn file_list(&self) -> Result<Vec<String>, ModError> {
+            Ok(self.files.keys().cloned().collect())
+        }
+        fn file_list_in_folder(
+            &self,
+            folder: &str,
+            _recursive: bool,
+        ) -> Result<Vec<String>, ModError> {
+            Ok(self
+                .files
+                .keys()
+                .filter(|p| p.starts_with(folder))
+                .cloned()
+                .collect())
+        }
+        fn read_file(&self, path: &str) -> Result<Vec<u8>, ModError> {
+            let mut data = Vec::new();
+            self.read_file_stream(path)?.read_to_end(&mut data)?;
+            Ok(data)
+        }
+        fn read_file_stream(&self, path: &str) -> Result<Box<dyn Read + '_>, ModError> {
+            match self.files.get(path) {
+                Some(Some(data)) => Ok(Box::new(data.as_slice())),
+                Some(None) => Ok(Box::new(FailingReader)),
+                None => Err(ModError::FileNotFound(path.to_string())),
+            }
+        }
+        fn has_script(&self) -> bool {
+            false
+        }
+        fn script_content(&self) -> Option<(ScriptType, String)> {
+            None
+        }
+        fn screenshot_path(&self) -> Option<&str> {
+            self.screenshot_path.as_deref()
+        }
+    }
+
+    #[test]
+    fn test_screenshot_bytes() {
+        let mut archive = MockMod::new(&[
+            ("Data/MyMod.esp", Some(b"TES4")),
+            ("screenshot.png", Some(b"\x89PNG")),
+        ]);
+        assert_eq!(archive.screenshot_bytes().unwrap(), None);
+
+        archive.screenshot_path = Some("screenshot.png".to_string());
+        assert_eq!(
+            archive.screenshot_bytes().unwrap(),
+            Some(b"\x89PNG".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_screenshot_bytes_missing_file() {
+        let mut archive = MockMod::new(&[("Data/MyMod.esp", Some(b"TES4"))]);
+        archive.screenshot_path = Some("screenshot.png".to_string());
+        assert!(matches!(
+            archive.screenshot_bytes(),
+            Err(ModError::FileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_contents_ok() {
+        let archive = MockMod::new(&[
+            ("Data/MyMod.esp", Some(b"TES4")),
+            ("Data/textures/sky.dds", Some(b"DDS ")),
+        ]);
+        assert!(archive.verify_contents().is_ok());
+    }
+
+    #[test]
+    fn test_verify_contents_read_failure() {
+        let archive = MockMod::new(&[
+            ("Data/MyMod.esp", Some(b"TES4")),
+            ("Data/textures/sky.dds", None),
+        ]);
+
+        match archive.verify_contents() {
+            Err(ModError::ArchiveError(msg)) => {
+                assert!(msg.contains("Data/textures/sky.dds"));
+                assert!(msg.contains("CRC mismatch"));
+            }
+            other => panic!("expected ArchiveError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_mod_info_builder() {
+        let info = ModInfo::new("Test Mod", "TestMod.7z")
+            .with_version("1.0.0")
+            .with_author("Test Author");
+
+        assert_eq!(info.name, "Test Mod");
+        assert_eq!(info.file_name, "TestMod.7z");
+        assert_eq!(info.version, "1.0.0");
+        assert_eq!(info.author, Some("Test Author".into()));
+    }
+
+    #[test]
+    fn test_with_install_date() {
+        let installed = "2024-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let info = ModInfo::new("Test Mod", "TestMod.7z").with_install_date(installed);
+        assert_eq!(info.install_date, Some(installed));
+
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: ModInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.install_date, Some(installed));
+    }
+
+    #[test]
+    fn test_from_file_name_nexus() {
+        let info = ModInfo::from_file_name("SkyUI_5_2SE-12604-5-2SE.7z");
+        assert_eq!(info.name, "SkyUI 5 2SE");
+        assert_eq!(info.file_name, "SkyUI_5_2SE-12604-5-2SE.7z");
+        assert_eq!(info.id, Some("12604".into()));
+        assert_eq!(info.version, "5.2SE");
+
+        let info = ModInfo::from_file_name("SkyUI_5_2SE-12604-5-2SE-1573676004.7z");
+        assert_eq!(info.name, "SkyUI 5 2SE");
+        assert_eq!(info.id, Some("12604".into()));
+        assert_eq!(info.version, "5.2SE");
+
+        let info = ModInfo::from_file_name("Unofficial Skyrim Special Edition Patch-266-4-2-5.7z");
+        assert_eq!(info.name, "Unofficial Skyrim Special Edition Patch");
+        assert_eq!(info.id, Some("266".into()));
+        assert_eq!(info.version, "4.2.5");
+    }
+
+    #[test]
+    fn test_from_file_name_plain() {
+        let info = ModInfo::from_file_name("MyMod.zip");
+        assert_eq!(info.name, "MyMod");
+        assert_eq!(info.file_name, "MyMod.zip");
+        assert_eq!(info.id, None);
+        assert_eq!(info.version, "");
+
+        let info = ModInfo::from_file_name("Better-Looking-Trees.7z");
+        assert_eq!(info.name, "Better-Looking-Trees");
+        assert_eq!(info.id, None);
+    }
+
+    #[test]
+    fn test_dirty_tracking() {
+        let info = ModInfo::new("Test Mod", "TestMod.7z");
+        assert!(!info.is_dirty());
+
+        let mut info = info.with_version("1.0.0");
+        assert!(info.is_dirty());
+
+        info.mark_clean();
+        assert!(!info.is_dirty());
+
+        let info = info.with_author("Test Author");
+        assert!(info.is_dirty());
+    }
+
+    #[test]
+    fn test_dirty_not_serialized() {
+        let info = ModInfo::new("Test Mod", "TestMod.7z").with_version("1.0.0");
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(!json.contains("dirty"));
+
+        let parsed: ModInfo = serde_json::from_str(&json).unwrap();
+        assert!(!parsed.is_dirty());
+    }
+
+    #[test]
+    fn test_mod_info_serialization() {
+        let info = ModInfo::new("Test Mod", "TestMod.7z").with_version("1.0.0");
+
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: ModInfo = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.name, info.name);
+        assert_eq!(parsed.version, info.version);
+    }
+
+    #[test]
+    fn test_parse_version_strict() {
+        assert_eq!(
+            ModInfo::parse_version_strict("1.2.3").unwrap(),
+            semver::Version::new(1, 2, 3)
+        );
+        assert_eq!(
+            ModInfo::parse_version_strict("v2.0.0").unwrap(),
+            semver::Version::new(2, 0, 0)
+        );
+        assert!(matches!(
+            ModInfo::parse_version_strict("1.2"),
+            Err(VersionParseError::Invalid { ref input, .. }) if input == "1.2"
+        ));
+        assert!(matches!(
+            ModInfo::parse_version_strict(""),
+            Err(VersionParseError::Empty)
+        ));
+        assert!(matches!(
+            ModInfo::parse_version_strict("v"),
+            Err(VersionParseError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_screenshot_split_round_trip() {
+        let info = ModInfo {
+            screenshot: Some(vec![1, 2, 3]),
+            ..ModInfo::new("Test Mod", "TestMod.7z")
+                .with_version("1.0.0")
+                .with_author("Test Author")
+        };
+
+        let (lite, screenshot) = info.clone().strip_screenshot();
+        assert_eq!(lite.name, "Test Mod");
+        assert_eq!(lite.author, Some("Test Author".into()));
+        assert_eq!(screenshot, Some(vec![1, 2, 3]));
+
+        let json = serde_json::to_string(&lite).unwrap();
+        assert!(!json.contains("screenshot"));
+
+        let merged = lite.with_screenshot(screenshot);
+        assert_eq!(merged.name, info.name);
+        assert_eq!(merged.version, info.version);
+        assert_eq!(merged.screenshot, info.screenshot);
+    }
+
+    #[test]
+    fn test_screenshot_split_without_screenshot() {
+        let (lite, screenshot) = ModInfo::new("Test Mod", "TestMod.7z").strip_screenshot();
+        assert!(screenshot.is_none());
+        assert!(lite.with_screenshot(None).screenshot.is_none());
+    }
+
+    #[test]
+    fn test_normalize_trims() {
+        let mut info = ModInfo::new("  Test Mod\t", "TestMod.7z")
+            .with_version(" 1.0.0 ")
+            .with_author(" Test Author ");
+        info.normalize();
+
+        assert_eq!(info.name, "Test Mod");
+        assert_eq!(info.version, "1.0.0");
+        assert_eq!(info.author, Some("Test Author".into()));
+    }
+
+    #[test]
+    fn test_normalize_marks_dirty() {
+        let mut info = ModInfo::new(" Test Mod", "TestMod.7z");
+        info.mark_clean();
+        info.normalize();
+        assert!(info.is_dirty());
+
+        let mut info = ModInfo::new("Test Mod", "TestMod.ZIP");
+        info.mark_clean();
+        info.normalize();
+        assert!(info.is_dirty());
+
+        let mut clean = ModInfo::new("Test Mod", "TestMod.7z").with_author("Author");
+        clean.mark_clean();
+        clean.normalize();
+        assert!(!clean.is_dirty());
+    }
+
+    #[test]
+    fn test_normalize_empty_description() {
+        let mut info = ModInfo {
+            description: Some(String::new()),
+            ..ModInfo::new("Test Mod", "TestMod.7z")
+        };
+        info.normalize();
+        assert_eq!(info.description, None);
+
+        let mut info = ModInfo {
+            description: Some("  ".into()),
+            author: Some(" ".into()),
+            ..ModInfo::new("Test Mod", "TestMod.7z")
+        };
+        info.normalize();
+        assert_eq!(info.description, None);
+        assert_eq!(info.author, None);
+
+        let mut info = ModInfo {
+            description: Some("A mod.".into()),
+            ..ModInfo::new("Test Mod", "TestMod.7z")
+        };
+        info.normalize();
+        assert_eq!(info.description, Some("A mod.".into()));
+    }
+
+    #[test]
+    fn test_normalize_file_name_extension() {
+        let mut info = ModInfo::new("Test Mod", "Mod.ZIP");
+        info.normalize();
+        assert_eq!(info.file_name, "Mod.zip");
+
+        let mut info = ModInfo::new("Test Mod", "My.Mod.7Z");
+        info.normalize();
+        assert_eq!(info.file_name, "My.Mod.7z");
+
+        let mut info = ModInfo::new("Test Mod", "README");
+        info.normalize();
+        assert_eq!(info.file_name, "README");
+    }
+
+    #[test]
+    fn test_normalize_file_name_without_extension() {
+        for name in [
+            "SkyUI 5.2SE",
+            "SkyUI 5.2 SE",
+            "Patch.Version3.ESPFILE",
+            "Mod.",
+        ] {
+            let mut info = ModInfo::new("Test Mod", name);
+            info.normalize();
+            assert_eq!(info.file_name, name);
+            assert!(!info.is_dirty());
+        }
+    }
+
+    #[test]
+    fn test_as_query_document() {
+        let info = ModInfo {
+            description: Some("A  better\nInventory UI".into()),
+            website: Some(
+                "https://www.nexusmods.com/skyrimspecialedition/mods/12604"
+                    .parse()
+                    .unwrap(),
+            ),
+            ..ModInfo::new("SkyUI", "SkyUI.7z").with_author("schlangster")
+        };
+
+        assert_eq!(
+            info.as_query_document(),
+            "skyui schlangster a better inventory ui www.nexusmods.com"
+        );
+    }
+
+    #[test]
+    fn test_matches_query() {
+        let info = ModInfo {
+            description: Some("Elegant, PC-friendly interface".into()),
+            ..ModInfo::new("SkyUI", "SkyUI.7z").with_author("schlangster")
+        };
+
+        assert!(info.matches_query(&["skyui"]));
+        assert!(info.matches_query(&["Interface", "schlangster"]));
+        assert!(info.matches_query(&[]));
+        assert!(info.matches_query(&["  "]));
+        assert!(!info.matches_query(&["skyui", "textures"]));
+        assert!(!info.matches_query(&["SkyUI.7z"]));
+    }
+
+    #[test]
+    fn test_next_check_due_deterministic() {
+        let now = Utc::now();
+        let interval = Duration::hours(24);
+        let info = ModInfo {
+            last_update_check: Some(now),
+            ..ModInfo::new("SkyUI", "SkyUI_5_2SE-12604-5-2SE.7z")
+        };
+
+        let due = info.next_check_due(now, interval, 42);
+        assert_eq!(due, info.next_check_due(now, interval, 42));
+
+        let earliest = now + interval - Duration::hours(24) / 10;
+        let latest = now + interval + Duration::hours(24) / 10;
+        assert!(due >= earliest && due <= latest);
+    }
+
+    #[test]
+    fn test_next_check_due_differs_per_mod() {
+        let now = Utc::now();
+        let interval = Duration::hours(24);
+        let a = ModInfo {
+            last_update_check: Some(now),
+            ..ModInfo::new("SkyUI", "SkyUI_5_2SE-12604-5-2SE.7z")
+        };
+        let b = ModInfo {
+            last_update_check: Some(now),
+            ..ModInfo::new(
+                "USSEP",
+                "Unofficial Skyrim Special Edition Patch-266-4-2-5.7z",
+            )
+        };
+
+        assert_ne!(
+            a.next_check_due(now, interval, 42),
+            b.next_check_due(now, interval, 42)
+        );
+    }
+
+    #[test]
+    fn test_next_check_due_never_checked() {
+        let now = Utc::now();
+        let interval = Duration::hours(24);
+        let info = ModInfo::new("SkyUI", "SkyUI.7z");
+
+        let due = info.next_check_due(now, interval, 7);
+        assert!(due >= now && due <= now + interval / 10);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_screenshot_thumbnail() {
+        let source = image::RgbImage::from_pixel(64, 32, image::Rgb([200, 30, 30]));
+        let mut png = std::io::Cursor::new(Vec::new());
+        source.write_to(&mut png, image::ImageFormat::Png).unwrap();
+
+        let mut info = ModInfo::new("SkyUI", "SkyUI.7z");
+        assert!(info.screenshot_thumbnail(16).unwrap().is_none());
+
+        info.screenshot = Some(png.into_inner());
+        let thumb = info.screenshot_thumbnail(16).unwrap().unwrap();
+        let thumb = image::load_from_memory(&thumb).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (16, 8));
+
+        let full = info.screenshot_thumbnail(128).unwrap().unwrap();
+        let full = image::load_from_memory(&full).unwrap();
+        assert_eq!((full.width(), full.height()), (64, 32));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_screenshot_thumbnail_jpeg() {
+        let source = image::RgbImage::from_pixel(40, 80, image::Rgb([30, 120, 200]));
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        source
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .unwrap();
+
+        let info = ModInfo {
+            screenshot: Some(jpeg.into_inner()),
+            ..ModInfo::new("SkyUI", "SkyUI.7z")
+        };
+        let thumb = info.screenshot_thumbnail(20).unwrap().unwrap();
+        assert_eq!(
+            image::guess_format(&thumb).unwrap(),
+            image::ImageFormat::Png
+        );
+        let thumb = image::load_from_memory(&thumb).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (10, 20));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_screenshot_thumbnail_invalid() {
+        let info = ModInfo {
+            screenshot: Some(b"not an image".to_vec()),
+            ..ModInfo::new("SkyUI", "SkyUI.7z")
+        };
+        assert!(info.screenshot_thumbnail(16).is_err());
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut info = ModInfo::new("SkyUI", "SkyUI.7z").with_tags(["UI", "Essential"]);
+        assert!(info.has_tag("ui"));
+        assert!(info.has_tag("ESSENTIAL"));
+        assert!(!info.has_tag("Textures"));
+
+        info.mark_clean();
+        info.add_tag("essential");
+        assert!(!info.is_dirty());
+        assert_eq!(info.tags.len(), 2);
+
+        info.add_tag("Überarbeitet");
+        assert!(info.is_dirty());
+        assert!(info.has_tag("ÜBERARBEITET"));
+    }
+
+    #[test]
+    fn test_tags_serde() {
+        let info: ModInfo =
+            serde_json::from_str(r#"{"name":"Old","file_name":"Old.7z","version":"1.0"}"#).unwrap();
+        assert!(info.tags.is_empty());
+
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(!json.contains("tags"));
+
+        let tagged = info.with_tags(["UI"]);
+        let parsed: ModInfo =
+            serde_json::from_str(&serde_json::to_string(&tagged).unwrap()).unwrap();
+        assert_eq!(parsed.tags, vec!["UI"]);
+    }
+
+    #[test]
+    fn test_enabled_default() {
+        assert!(ModInfo::default().is_enabled());
+        assert!(ModInfo::new("SkyUI", "SkyUI.7z").is_enabled());
+        assert!(ModInfoLite::default().enabled);
+
+        let old: ModInfo =
+            serde_json::from_str(r#"{"name":"Old","file_name":"Old.7z","version":"1.0"}"#).unwrap();
+        assert!(old.is_enabled());
+    }
+
+    #[test]
+    fn test_set_enabled() {
+        let mut info = ModInfo::new("SkyUI", "SkyUI.7z");
+        info.set_enabled(true);
+        assert!(!info.is_dirty());
+
+        info.set_enabled(false);
+        assert!(info.is_dirty());
+        assert!(!info.is_enabled());
+
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: ModInfo = serde_json::from_str(&json).unwrap();
+        assert!(!parsed.is_enabled());
+        assert_eq!(parsed.diff(&info), vec![]);
+
+        let mut merged = ModInfo::new("SkyUI", "SkyUI.7z");
+        merged.update_from(&info, true);
+        assert!(merged.is_enabled());
+    }
+
+    #[test]
+    fn test_update_from_fill_and_overwrite() {
+        let remote = ModInfo {
+            id: Some("3863".to_string()),
+            category_id: Some(42),
+            ..ModInfo::new("SkyUI", "").with_version("5.2")
+        };
+
+        let mut filled = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.1");
+        filled.mark_clean();
+        filled.update_from(&remote, false);
+        assert!(filled.is_dirty());
+        assert_eq!(filled.version, "5.1");
+        assert_eq!(filled.file_name, "SkyUI.7z");
+        assert_eq!(filled.id.as_deref(), Some("3863"));
+        assert_eq!(filled.category_id, Some(42));
+
+        let mut overwritten = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.1");
+        overwritten.update_from(&remote, true);
+        assert_eq!(overwritten.version, "5.2");
+        assert_eq!(overwritten.file_name, "SkyUI.7z");
+
+        let mut unchanged = overwritten.clone();
+        unchanged.mark_clean();
+        unchanged.update_from(&remote, true);
+        assert!(!unchanged.is_dirty());
+    }
+
+    #[test]
+    fn test_update_from_empty_optional_strings() {
+        let blank = ModInfo {
+            id: Some(String::new()),
+            author: Some(String::new()),
+            description: Some(String::new()),
+            ..ModInfo::default()
+        };
+
+        let mut populated = ModInfo {
+            id: Some("3863".to_string()),
+            description: Some("A mod.".to_string()),
+            ..ModInfo::new("SkyUI", "SkyUI.7z").with_author("schlangster")
+        };
+        populated.mark_clean();
+        populated.update_from(&blank, true);
+        assert!(!populated.is_dirty());
+        assert_eq!(populated.id.as_deref(), Some("3863"));
+        assert_eq!(populated.author.as_deref(), Some("schlangster"));
+        assert_eq!(populated.description.as_deref(), Some("A mod."));
+
+        let mut filled = blank.clone();
+        filled.update_from(&populated, false);
+        assert_eq!(filled.id.as_deref(), Some("3863"));
+        assert_eq!(filled.author.as_deref(), Some("schlangster"));
+        assert_eq!(filled.description.as_deref(), Some("A mod."));
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let from_file_name = ModInfo::from_file_name("SkyUI_5_2_SE-12604-5-2SE.7z");
+        let from_nexus = ModInfo {
+            name: "SkyUI (Nexus)".to_string(),
+            category_id: Some(42),
+            website: Some(
+                "https://www.nexusmods.com/skyrimspecialedition/mods/12604"
+                    .parse()
+                    .unwrap(),
+            ),
+            ..ModInfo::default().with_author("schlangster")
+        };
+        let from_cache = ModInfo {
+            description: Some("Elegant, PC-friendly interface mod".to_string()),
+            is_endorsed: Some(true),
+            ..ModInfo::default().with_author("someone else")
+        };
+
+        let merged = ModInfo::coalesce(&[from_file_name.clone(), from_nexus, from_cache]);
+        assert_eq!(merged.name, from_file_name.name);
+        assert_eq!(merged.id.as_deref(), Some("12604"));
+        assert_eq!(merged.author.as_deref(), Some("schlangster"));
+        assert_eq!(merged.category_id, Some(42));
+        assert!(merged.website.is_some());
+        assert!(merged.description.is_some());
+        assert_eq!(merged.is_endorsed, Some(true));
+
+        assert_eq!(ModInfo::coalesce(&[]).name, "");
+    }
+
+    fn ordered(name: &str, order: i32) -> ModInfo {
+        ModInfo {
+            load_order: Some(order),
+            ..ModInfo::new(name, format!("{}.7z", name))
+        }
+    }
+
+    #[test]
+    fn test_json_stream_round_trip() {
+        let info = ModInfo {
+            id: Some("3863".to_string()),
+            category_id: Some(42),
+            ..display_info()
+        };
+
+        let mut buf = Cursor::new(Vec::new());
+        info.to_writer(&mut buf).unwrap();
+        buf.set_position(0);
+        let parsed = ModInfo::from_reader(&mut buf).unwrap();
+        assert_eq!(parsed.diff(&info), vec![]);
+
+        let mods = vec![info, ModInfo::new("Bare", "Bare.7z")];
+        let mut buf = Cursor::new(Vec::new());
+        write_mods_json(&mut buf, &mods).unwrap();
+        buf.set_position(0);
+        let parsed = read_mods_json(&mut buf).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].diff(&mods[0]), vec![]);
+        assert_eq!(parsed[1].diff(&mods[1]), vec![]);
+    }
+
+    #[test]
+    fn test_read_mods_json_invalid() {
+        assert!(read_mods_json(Cursor::new(b"{\"name\": 1}")).is_err());
+    }
+
+    #[test]
+    fn test_from_legacy_json() {
+        let legacy = serde_json::json!({
+            "Id": 12604,
+            "DownloadId": "",
+            "ModName": "SkyUI",
+            "FileName": "SkyUI_5_2_SE-12604-5-2SE.7z",
+            "HumanReadableVersion": "5.2SE",
+            "LastKnownVersion": "5.2SE",
+            "MachineVersion": "5.2.0.0",
+            "Author": "schlangster",
+            "Description": "",
+            "CategoryId": 42,
+            "CustomCategoryId": -1,
+            "Website": "https://www.nexusmods.com/skyrimspecialedition/mods/12604",
+            "InstallDate": "2019-11-13T20:13:24",
+            "DownloadDate": "not a date",
+            "IsEndorsed": true,
+            "UpdateWarningEnabled": true,
+            "PlaceInModLoadOrder": 3,
+        });
+
+        let info = ModInfo::from_legacy_json(legacy).unwrap();
+        assert_eq!(info.id.as_deref(), Some("12604"));
+        assert_eq!(info.download_id, None);
+        assert_eq!(info.name, "SkyUI");
+        assert_eq!(info.file_name, "SkyUI_5_2_SE-12604-5-2SE.7z");
+        assert_eq!(info.version, "5.2SE");
+        assert_eq!(info.machine_version, Some(semver::Version::new(5, 2, 0)));
+        assert_eq!(info.author.as_deref(), Some("schlangster"));
+        assert_eq!(info.description, None);
+        assert_eq!(info.category_id, Some(42));
+        assert_eq!(info.custom_category_id, None);
+        assert!(info.website_is_nexus());
+        assert_eq!(
+            info.install_date.unwrap().to_rfc3339(),
+            "2019-11-13T20:13:24+00:00"
+        );
+        assert_eq!(info.download_date, None);
+        assert_eq!(info.is_endorsed, Some(true));
+        assert_eq!(info.load_order, Some(3));
+        assert!(info.is_enabled());
+    }
+
+    #[test]
+    fn test_from_legacy_json_sentinels() {
+        let legacy = serde_json::json!({
+            "ModName": "Loose Files",
+            "CategoryId": -1,
+            "CustomCategoryId": 7,
+            "PlaceInModLoadOrder": -1,
+            "NewPlaceInModLoadOrder": -1,
+        });
+
+        let info = ModInfo::from_legacy_json(legacy).unwrap();
+        assert_eq!(info.category_id, None);
+        assert_eq!(info.custom_category_id, Some(7));
+        assert_eq!(info.load_order, None);
+        assert_eq!(info.new_load_order, None);
+    }
+
+    #[test]
+    fn test_legacy_version() {
+        let v = |major, minor, patch| Some(semver::Version::new(major, minor, patch));
+        assert_eq!(legacy_version("5.2.0.0"), v(5, 2, 0));
+        assert_eq!(legacy_version("1.6.640.8"), v(1, 6, 640));
+        assert_eq!(legacy_version("1.2.3"), v(1, 2, 3));
+        assert_eq!(legacy_version("3.1"), v(3, 1, 0));
+        assert_eq!(legacy_version("4"), v(4, 0, 0));
+        assert_eq!(
+            legacy_version("1.0.0-beta.2"),
+            Some(semver::Version::parse("1.0.0-beta.2").unwrap())
+        );
+
+        for invalid in ["", "5.2SE", "1.2.3.4.5", "1..2", "-1.0"] {
+            assert_eq!(legacy_version(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_from_legacy_json_invalid() {
+        assert!(ModInfo::from_legacy_json(serde_json::json!([1, 2])).is_err());
+        assert!(ModInfo::from_legacy_json(serde_json::json!({ "ModName": 5 })).is_err());
+    }
+
+    #[test]
+    fn test_commit_load_order() {
+        let mut info = ordered("A", 3);
+        info.mark_clean();
+
+        info.commit_load_order();
+        assert!(!info.is_dirty());
+        assert_eq!(info.load_order, Some(3));
+
+        info.new_load_order = Some(0);
+        info.commit_load_order();
+        assert!(info.is_dirty());
+        assert_eq!(info.load_order, Some(0));
+        assert_eq!(info.new_load_order, None);
+    }
+
+    #[test]
+    fn test_validate_load_order_permutation() {
+        let mods = [ordered("A", 2), ordered("B", 0), ordered("C", 1)];
+        assert!(validate_load_order(&mods).is_ok());
+        assert!(validate_load_order(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_load_order_gap() {
+        let mods = [ordered("A", 0), ordered("B", 1), ordered("C", 3)];
+        let err = validate_load_order(&mods).unwrap_err();
+        assert_eq!(err, "C has load order 3, outside 0..3");
+
+        let unset = [ordered("A", 0), ModInfo::new("B", "B.7z")];
+        assert_eq!(
+            validate_load_order(&unset).unwrap_err(),
+            "B has no load order"
+        );
+    }
+
+    #[test]
+    fn test_validate_load_order_duplicate() {
+        let mods = [ordered("A", 0), ordered("B", 1), ordered("C", 1)];
+        let err = validate_load_order(&mods).unwrap_err();
+        assert_eq!(err, "B and C share load order 1");
+    }
+
+    #[test]
+    fn test_next_check_due_huge_interval_saturates() {
+        let now = Utc::now();
+        let info = ModInfo {
+            last_update_check: Some(now),
+            ..ModInfo::new("SkyUI", "SkyUI.7z")
+        };
+
+        assert_eq!(
+            info.next_check_due(now, Duration::MAX, 7),
+            DateTime::<Utc>::MAX_UTC
+        );
+        assert_eq!(
+            info.next_check_due(now, Duration::MIN, 7),
+            DateTime::<Utc>::MIN_UTC
+        );
+
+        let never = ModInfo::new("SkyUI", "SkyUI.7z");
+        assert!(never.next_check_due(now, Duration::MAX, 7) >= now);
+    }
+
+    #[test]
+    fn test_should_prompt_endorsement_abstain_window() {
+        let now = Utc::now();
+        let mut info = ModInfo::new("SkyUI", "SkyUI.7z");
+        assert!(info.should_prompt_endorsement(now));
+
+        info.abstain_until(now + Duration::days(30));
+        assert!(info.is_dirty());
+        assert!(!info.should_prompt_endorsement(now));
+        assert!(info.should_prompt_endorsement(now + Duration::days(31)));
+    }
+
+    #[test]
+    fn test_should_prompt_endorsement_known_state() {
+        let now = Utc::now();
+        let mut info = ModInfo::new("SkyUI", "SkyUI.7z");
+        info.abstain_until(now - Duration::days(1));
+        assert!(info.should_prompt_endorsement(now));
+
+        info.is_endorsed = Some(false);
+        assert!(!info.should_prompt_endorsement(now));
+    }
+
+    #[test]
+    fn test_semantic_key_matches() {
+        let a = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.2 SE");
+        let b = ModInfo::new("skyui", "skyui.zip").with_version("5.2");
+        assert_eq!(a.semantic_key(), b.semantic_key());
+
+        let c = ModInfo::new("  Sky   UI ", "SkyUI.7z").with_version("v5.2.1");
+        assert_eq!(c.semantic_key(), "sky-ui@5.2");
+
+        let d = ModInfo {
+            machine_version: Some(semver::Version::new(5, 2, 0)),
+            ..ModInfo::new("SkyUI", "SkyUI.7z").with_version("Release Candidate")
+        };
+        assert_eq!(d.semantic_key(), a.semantic_key());
+    }
+
+    #[test]
+    fn test_semantic_key_differs() {
+        let a = ModInfo::new("SkyUI", "SkyUI.7z").with_version("5.2");
+        let b = ModInfo::new("SkyUI Survival", "SkyUISurvival.7z").with_version("5.2");
+        let c = ModInfo::new("SkyUI", "SkyUI.7z").with_version("4.1");
+        assert_ne!(a.semantic_key(), b.semantic_key());
+        assert_ne!(a.semantic_key(), c.semantic_key());
+
+        let unversioned = ModInfo::new("SkyUI", "SkyUI.7z");
+        assert_eq!(unversioned.semantic_key(), "skyui");
+    }
+
+    fn with_website(url: &str) -> ModInfo {
+        ModInfo {
+            website: Some(url.parse().unwrap()),
+            ..ModInfo::new("Test Mod", "TestMod.7z")
+        }
+    }
+
+    #[test]
+    fn test_website_nexus_https() {
+        let info = with_website("https://www.nexusmods.com/skyrimspecialedition/mods/12604");
+        assert!(info.website_is_nexus());
+        assert!(info.website_scheme_ok());
+
+        assert!(with_website("https://nexusmods.com/").website_is_nexus());
+    }
+
+    #[test]
+    fn test_website_http() {
+        let info = with_website("http://www.nexusmods.com/skyrim/mods/3863");
+        assert!(info.website_is_nexus());
+        assert!(!info.website_scheme_ok());
+    }
+
+    #[test]
+    fn test_website_not_nexus() {
+        let info = with_website("https://github.com/schlangster/skyui");
+        assert!(!info.website_is_nexus());
+        assert!(info.website_scheme_ok());
+
+        assert!(!with_website("https://evilnexusmods.com/").website_is_nexus());
+        assert!(!with_website("https://nexusmods.com.example.org/").website_is_nexus());
+
+        let none = ModInfo::new("Test Mod", "TestMod.7z");
+        assert!(!none.website_is_nexus());
+        assert!(!none.website_scheme_ok());
+    }
+
+    #[test]
+    fn test_estimated_size_bytes() {
+        let empty = ModInfo::default();
+        let named = ModInfo::new("Test Mod", "TestMod.7z").with_author("Test Author");
+        let with_screenshot = ModInfo {
+            screenshot: Some(vec![0; 512 * 1024]),
+            ..named.clone()
+        };
+
+        assert!(named.estimated_size_bytes() > empty.estimated_size_bytes());
+        assert!(
+            with_screenshot.estimated_size_bytes() >= empty.estimated_size_bytes() + 512 * 1024
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = ModInfo::new("Test Mod", "TestMod.7z")
+            .with_version("1.0.0")
+            .with_author("Old Author");
+        let new = old.clone().with_version("1.1.0").with_author("New Author");
+
+        let changes = old.diff(&new);
+        assert_eq!(
+            changes,
+            vec![
+                ModInfoFieldChange {
+                    field: "version",
+                    old: Some("1.0.0".into()),
+                    new: Some("1.1.0".into()),
+                },
+                ModInfoFieldChange {
+                    field: "author",
+                    old: Some("Old Author".into()),
+                    new: Some("New Author".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_unset_and_screenshot() {
+        let old = ModInfo::new("Test Mod", "TestMod.7z").with_author("Author");
+        let new = ModInfo {
+            author: None,
+            screenshot: Some(vec![0; 16]),
+            ..old.clone()
+        };
+
+        let changes = old.diff(&new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "author");
+        assert_eq!(changes[0].new, None);
+        assert_eq!(changes[1].field, "screenshot");
+        assert_eq!(changes[1].new, Some("16 bytes".into()));
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let info = ModInfo::new("Test Mod", "TestMod.7z").with_version("1.0.0");
+        assert!(info.diff(&info.clone()).is_empty());
+    }
+
+    struct MockCategories;
+
+    impl CategoryResolver for MockCategories {
+        fn category_name(&self, category_id: i32) -> Option<&str> {
+            match category_id {
+                1 => Some("UI"),
+                2 => Some("Textures"),
+                _ => None,
+            }
+        }
+    }
+
+    fn display_info() -> ModInfo {
+        ModInfo {
+            category_id: Some(1),
+            ..ModInfo::new("SkyUI", "SkyUI.7z")
+                .with_version("5.2")
+                .with_author("schlangster")
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(display_info().to_string(), "SkyUI v5.2 by schlangster");
+        assert_eq!(ModInfo::new("Bare", "Bare.7z").to_string(), "Bare");
+    }
+
+    #[test]
+    fn test_display_with_default_matches_display() {
+        let info = display_info();
+        assert_eq!(
+            info.display_with(&ModInfoDisplay::default()),
+            info.to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_localized_default_matches_display() {
+        let info = display_info();
+        assert_eq!(
+            info.display_localized(&ModInfoLabels::default()),
+            info.to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_localized_custom_labels() {
+        let labels = ModInfoLabels {
+            version_prefix: "Version ".to_string(),
+            author_connector: "von".to_string(),
+        };
+        assert_eq!(
+            display_info().display_localized(&labels),
+            "SkyUI Version 5.2 von schlangster"
+        );
+        assert_eq!(
+            ModInfo::new("Bare", "Bare.7z").display_localized(&labels),
+            "Bare"
+        );
+    }
+
+    #[test]
+    fn test_display_with_options() {
+        let info = display_info();
+        let categories = MockCategories;
+
+        let cases = [
+            (false, false, false, "SkyUI"),
+            (true, false, false, "SkyUI v5.2"),
+            (false, true, false, "SkyUI by schlangster"),
+            (true, true, false, "SkyUI v5.2 by schlangster"),
+            (false, false, true, "SkyUI [UI]"),
+            (true, false, true, "SkyUI v5.2 [UI]"),
+            (false, true, true, "SkyUI by schlangster [UI]"),
+            (true, true, true, "SkyUI v5.2 by schlangster [UI]"),
+        ];
+
+        for (version, author, category, expected) in cases {
+            let resolver: Option<&dyn CategoryResolver> = category.then_some(&categories);
+            let opts = ModInfoDisplay::new()
+                .show_version(version)
+                .show_author(author)
+                .show_category(resolver);
+            assert_eq!(info.display_with(&opts), expected);
+        }
+    }
+
+    #[test]
+    fn test_display_with_category_resolution() {
+        let categories = MockCategories;
+        let opts = ModInfoDisplay::new().show_category(Some(&categories));
+
+        let custom = ModInfo {
+            custom_category_id: Some(2),
+            ..display_info()
+        };
+        assert_eq!(
+            custom.display_with(&opts),
+            "SkyUI v5.2 by schlangster [Textures]"
+        );
+
+        let unknown = ModInfo {
+            category_id: Some(99),
+            ..display_info()
+        };
+        assert_eq!(unknown.display_with(&opts), "SkyUI v5.2 by schlangster");
     }
 }